@@ -4,17 +4,33 @@ mod y2023;
 mod y2024;
 mod y2025;
 
-use clap::{command, Parser};
+use clap::{command, Parser, Subcommand};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
 use std::time::Instant;
-use util::solution::{Solution, SolverFn};
+use util::report::{self, Report};
+use util::scaling::{self, Measurement};
+use util::solution::{AnimationFn, GeneratorFn, Solution, SolverFn, VisualizerFn};
+use util::terminal::TerminalAnimation;
 use util::util::load_input;
+use util::visualize::Visualizer;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOCATOR: util::alloc::CountingAllocator = util::alloc::CountingAllocator::new();
 
 #[derive(Parser, Debug)]
 #[command(
     name = "Advent of Code Solver",
-    about = "Solve Advent of Code puzzles with optional year and day filtering."
+    about = "Solve Advent of Code puzzles with optional year and day filtering.",
+    args_conflicts_with_subcommands = true
 )]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Year to solve (default: all years [2022-2024])
     // #[arg(short, long)]
     year: Option<usize>,
@@ -23,41 +39,181 @@ struct Cli {
     // #[arg(short, long)]
     #[arg(num_args=1..=25)]
     days: Option<Vec<u8>>,
+
+    /// Write visual output of the days that support it to DIR/{year}/day{NN}/
+    #[arg(long, value_name = "DIR")]
+    visualize: Option<PathBuf>,
+
+    /// Play the simulation of the days that support it in the terminal
+    #[arg(long)]
+    animate: bool,
+
+    /// Frame rate of --animate
+    #[arg(long, default_value_t = 20.0, requires = "animate")]
+    fps: f64,
+
+    /// Write a self-contained HTML report of the answers, timings and images in outputs/ (and the --visualize DIR) to FILE
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print a random puzzle input for the given day
+    Gen {
+        /// Year of the puzzle
+        year: usize,
+
+        /// Day of the puzzle
+        day: u8,
+
+        /// Size of the input, what it counts depends on the day (e.g. nr of lines)
+        #[arg(short, long, default_value_t = 1000)]
+        size: usize,
+
+        /// Seed for the random number generator
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Time a day on inputs of growing size and estimate its complexity.
+    /// Inputs come from the day's generator, or are truncations (first lines) of the real input.
+    Scale {
+        /// Year of the puzzle
+        year: usize,
+
+        /// Day of the puzzle
+        day: u8,
+
+        /// Part to time
+        #[arg(short, long, default_value_t = 1)]
+        part: u8,
+
+        /// Smallest input size (default: 100 for generators, 1/16th of the real input otherwise)
+        #[arg(long)]
+        min_size: Option<usize>,
+
+        /// Largest input size (default: 10000 for generators, the full real input otherwise)
+        #[arg(long)]
+        max_size: Option<usize>,
+
+        /// Nr of sizes to time, growing geometrically from min to max
+        #[arg(long, default_value_t = 8)]
+        steps: usize,
+
+        /// Nr of runs per size, the fastest one is kept
+        #[arg(short, long, default_value_t = 3)]
+        repeat: usize,
+
+        /// Seed for the random number generator
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
 
+    match cli.command {
+        Some(Command::Gen {
+            year,
+            day,
+            size,
+            seed,
+        }) => {
+            match get_day_generator(year, day) {
+                Some(generate) => {
+                    println!("{}", generate(&mut StdRng::seed_from_u64(seed), size))
+                }
+                None => {
+                    eprintln!("No input generator for {} day {}", year, day);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some(Command::Scale {
+            year,
+            day,
+            part,
+            min_size,
+            max_size,
+            steps,
+            repeat,
+            seed,
+        }) => {
+            scale(year, day, part, min_size, max_size, steps, repeat, seed);
+            return;
+        }
+        None => {}
+    }
+
     let years = cli
         .year
         .map_or_else(|| (2022..=2024).collect(), |y| vec![y]);
     let default_days_max = if years.contains(&2025) { 12 } else { 25 };
     let days: Vec<u8> = cli.days.unwrap_or((1..=default_days_max).collect());
 
-    solve_with_time_tracking(years, days);
+    let animate = cli.animate.then_some(cli.fps);
+    solve_with_time_tracking(years, days, cli.visualize, animate, cli.report);
 }
 
-fn solve_with_time_tracking(years: Vec<usize>, days: Vec<u8>) {
+fn solve_with_time_tracking(
+    years: Vec<usize>,
+    days: Vec<u8>,
+    visualize: Option<PathBuf>,
+    animate_fps: Option<f64>,
+    report_path: Option<PathBuf>,
+) {
     let mut runtime = 0.0;
     let mut times: Vec<(f64, usize, u8, usize)> = Vec::new();
+    let mut report = report_path
+        .as_ref()
+        .map(|_| Report::new(report::parse_answers(&load_input("answers.txt"))));
 
     for year in years {
         println!("====== Year {} ======", year);
         for day in &days {
-            println!("=== Day {:02} ===", day);
             let (part_1, part_2) = get_day_solvers(year, day);
-            let input = load_input(&format!("inputs/{}/day_{}", year, day));
+            let input = load_input(&input_path(year, *day));
+            // Animations clear the screen, so they play before the answers are printed
+            let animation = animate_fps.map(|fps| (fps, get_day_animation(year, *day)));
+            if let Some((fps, Some(animate_day))) = animation {
+                animate_day(&input, &mut TerminalAnimation::new(fps));
+            }
+            println!("=== Day {:02} ===", day);
+            if let Some((_, None)) = animation {
+                println!("  · No animation for this day");
+            }
 
             let mut do_part = |solver: fn(&str) -> Solution, part_nr| {
-                let time = Instant::now();
-                let result = solver(&input);
-                let elapsed_ms = time.elapsed().as_nanos() as f64 / 1_000_000.0;
-                println!("  · Part {} ({:>9.4} ms): {}", part_nr, elapsed_ms, result);
+                reset_alloc_stats();
+                let (result, elapsed_ms) = time_solver(solver, &input);
+                println!(
+                    "  · Part {} ({:>9.4} ms{}): {}",
+                    part_nr,
+                    elapsed_ms,
+                    alloc_stats(),
+                    result
+                );
                 runtime += elapsed_ms;
                 times.push((elapsed_ms, year, *day, part_nr));
+                if let Some(report) = &mut report {
+                    report.add_part(year, *day, part_nr as u8, result.to_string(), elapsed_ms);
+                }
             };
             do_part(part_1, 1);
             do_part(part_2, 2);
+            if let Some(root) = &visualize {
+                // Not part of the timings, visualizing is usually much slower than solving
+                match get_day_visualizer(year, *day) {
+                    Some(visualize_day) => {
+                        let mut visualizer = Visualizer::new(root, year, *day);
+                        visualize_day(&input, &mut visualizer);
+                        println!("  · Visualized in {}", visualizer.dir().unwrap().display());
+                    }
+                    None => println!("  · No visualization for this day"),
+                }
+            }
             println!();
         }
     }
@@ -67,6 +223,160 @@ fn solve_with_time_tracking(years: Vec<usize>, days: Vec<u8>) {
         times.sort_by(|a, b| b.0.total_cmp(&a.0));
         println!("Slowest 5: {:?}", &times[..5]);
     }
+
+    if let (Some(mut report), Some(path)) = (report, report_path) {
+        report.add_images(Path::new("outputs"));
+        if let Some(root) = &visualize {
+            report.add_images(root);
+        }
+        report.write(&path);
+        println!("Report written to {}", path.display());
+    }
+}
+
+fn time_solver(solver: SolverFn, input: &str) -> (Solution, f64) {
+    let time = Instant::now();
+    let result = solver(input);
+    (result, time.elapsed().as_nanos() as f64 / 1_000_000.0)
+}
+
+#[cfg(feature = "alloc-stats")]
+fn reset_alloc_stats() {
+    ALLOCATOR.reset();
+}
+
+#[cfg(not(feature = "alloc-stats"))]
+fn reset_alloc_stats() {}
+
+/// Allocations since the last reset, to print next to the time
+#[cfg(feature = "alloc-stats")]
+fn alloc_stats() -> String {
+    format!(", {}", ALLOCATOR.stats())
+}
+
+#[cfg(not(feature = "alloc-stats"))]
+fn alloc_stats() -> String {
+    String::new()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn scale(
+    year: usize,
+    day: u8,
+    part: u8,
+    min_size: Option<usize>,
+    max_size: Option<usize>,
+    steps: usize,
+    repeat: usize,
+    seed: u64,
+) {
+    let (part_1, part_2) = get_day_solvers(year, &day);
+    let solver = if part == 1 { part_1 } else { part_2 };
+
+    // Inputs of a given size
+    let make_input: Box<dyn Fn(usize) -> String> = match get_day_generator(year, day) {
+        Some(generate) => {
+            println!(
+                "Timing {} day {} part {} on generated inputs",
+                year, day, part
+            );
+            Box::new(move |size| generate(&mut StdRng::seed_from_u64(seed), size))
+        }
+        None if Path::new(&input_path(year, day)).exists() => {
+            println!(
+                "Timing {} day {} part {} on the first lines of the input",
+                year, day, part
+            );
+            let input = load_input(&input_path(year, day));
+            Box::new(move |size| input.lines().take(size).collect::<Vec<&str>>().join("\n"))
+        }
+        None => {
+            eprintln!("No input generator or input for {} day {}", year, day);
+            std::process::exit(1);
+        }
+    };
+    let (min_size, max_size) = match get_day_generator(year, day) {
+        Some(_) => (min_size.unwrap_or(100), max_size.unwrap_or(10_000)),
+        None => {
+            let nr_of_lines = make_input(usize::MAX).lines().count();
+            let max_size = max_size.unwrap_or(nr_of_lines).min(nr_of_lines);
+            (min_size.unwrap_or(max_size / 16).max(1), max_size)
+        }
+    };
+
+    let mut measurements = Vec::new();
+    for size in scaling::geometric_sizes(min_size, max_size, steps) {
+        let input = make_input(size);
+        // Truncated inputs are not always valid, leave out sizes the solver can't handle
+        let fastest = panic::catch_unwind(AssertUnwindSafe(|| {
+            (0..repeat.max(1))
+                .map(|_| time_solver(solver, &input).1)
+                .fold(f64::INFINITY, f64::min)
+        }));
+        match fastest {
+            Ok(ms) => measurements.push(Measurement { size, ms }),
+            Err(_) => println!("Skipping size {}, the solver panicked", size),
+        }
+    }
+
+    println!("{}\n", scaling::table(&measurements));
+    println!("{}\n", scaling::plot(&measurements, 60, 15));
+    match scaling::fit_exponent(&measurements) {
+        Some(exponent) => println!(
+            "time ~ size^{:.2}, roughly {}",
+            exponent,
+            scaling::complexity_class(exponent)
+        ),
+        None => println!("Not enough sizes to estimate the complexity"),
+    }
+}
+
+fn input_path(year: usize, day: u8) -> String {
+    format!("inputs/{}/day_{}", year, day)
+}
+
+fn get_day_generator(year: usize, day: u8) -> Option<GeneratorFn> {
+    match (year, day) {
+        (2023, 7) => Some(y2023::day07::generate),
+        (2024, 9) => Some(y2024::day09::generate),
+        (2024, 14) => Some(y2024::day14::generate),
+        (2024, 20) => Some(y2024::day20::generate),
+        (2025, 5) => Some(y2025::day05::generate),
+        _ => None,
+    }
+}
+
+fn get_day_visualizer(year: usize, day: u8) -> Option<VisualizerFn> {
+    match (year, day) {
+        (2023, 14) => Some(y2023::day14::visualize),
+        (2023, 17) => Some(y2023::day17::visualize),
+        (2023, 18) => Some(y2023::day18::visualize),
+        (2023, 20) => Some(y2023::day20::visualize),
+        (2023, 21) => Some(y2023::day21::visualize),
+        (2023, 22) => Some(y2023::day22::visualize),
+        (2023, 25) => Some(y2023::day25::visualize),
+        (2024, 6) => Some(y2024::day06::visualize),
+        (2024, 14) => Some(y2024::day14::visualize),
+        (2024, 15) => Some(y2024::day15::visualize),
+        (2024, 16) => Some(y2024::day16::visualize),
+        (2024, 19) => Some(y2024::day19::visualize),
+        (2024, 23) => Some(y2024::day23::visualize),
+        (2024, 24) => Some(y2024::day24::visualize),
+        (2025, 7) => Some(y2025::day07::visualize),
+        (2025, 8) => Some(y2025::day08::visualize),
+        (2025, 9) => Some(y2025::day09::visualize),
+        (2025, 10) => Some(y2025::day10::visualize),
+        (2025, 11) => Some(y2025::day11::visualize),
+        _ => None,
+    }
+}
+
+fn get_day_animation(year: usize, day: u8) -> Option<AnimationFn> {
+    match (year, day) {
+        (2023, 16) => Some(y2023::day16::animate),
+        (2025, 7) => Some(y2025::day07::animate),
+        _ => None,
+    }
 }
 
 fn get_day_solvers(year: usize, day: &u8) -> (SolverFn, SolverFn) {
@@ -160,3 +470,72 @@ fn get_day_solvers(year: usize, day: &u8) -> (SolverFn, SolverFn) {
         _ => unimplemented!(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runs every solver that has a known answer against its input
+    // Inputs are private, so days without an input are skipped instead of failing the test
+    #[test]
+    fn test_answers() {
+        let answers = report::parse_answers(&load_input("answers.txt"));
+
+        let mut checked = 0;
+        let mut skipped = Vec::new();
+        let mut mismatches = Vec::new();
+        for ((year, day), parts) in answers {
+            let path = input_path(year, day);
+            if !Path::new(&path).exists() {
+                skipped.push(path);
+                continue;
+            }
+            let input = load_input(&path);
+            let (part_1, part_2) = get_day_solvers(year, &day);
+
+            for (part, expected) in parts {
+                let solver = if part == 1 { part_1 } else { part_2 };
+                checked += 1;
+                // A panicking solver is reported like a wrong answer, so the other days still run
+                match panic::catch_unwind(|| solver(&input).to_string()) {
+                    Ok(result) if result == expected => {}
+                    Ok(result) => mismatches.push(format!(
+                        "{} day {} part {}: expected {}, got {}",
+                        year, day, part, expected, result
+                    )),
+                    Err(_) => mismatches.push(format!(
+                        "{} day {} part {}: expected {}, but the solver panicked",
+                        year, day, part, expected
+                    )),
+                }
+            }
+        }
+
+        println!(
+            "Checked {} answers, skipped {} days without input",
+            checked,
+            skipped.len()
+        );
+        assert!(
+            mismatches.is_empty(),
+            "{} wrong answers:\n{}",
+            mismatches.len(),
+            mismatches.join("\n")
+        );
+    }
+
+    #[test]
+    fn test_generated_inputs_can_be_solved() {
+        for (year, day) in [(2023, 7), (2024, 9), (2024, 14), (2024, 20), (2025, 5)] {
+            let generate = get_day_generator(year, day).unwrap();
+            let (part_1, part_2) = get_day_solvers(year, &day);
+            for seed in 0..3 {
+                let input = generate(&mut StdRng::seed_from_u64(seed), 50);
+                // Same seed gives the same input
+                assert_eq!(input, generate(&mut StdRng::seed_from_u64(seed), 50));
+                part_1(&input);
+                part_2(&input);
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+/// Allocator that counts what goes through it before handing it to the system allocator.
+/// Installed as global allocator with the `alloc-stats` feature, see `main.rs`.
+pub struct CountingAllocator {
+    allocations: AtomicUsize,
+    bytes: AtomicUsize,
+    live: AtomicUsize,
+    peak: AtomicUsize,
+    baseline: AtomicUsize,
+}
+
+/// What was allocated since the last reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes: usize,
+    /// Most bytes that were live at the same time, on top of what was already live at the reset
+    pub peak_bytes: usize,
+}
+
+impl CountingAllocator {
+    pub const fn new() -> Self {
+        CountingAllocator {
+            allocations: AtomicUsize::new(0),
+            bytes: AtomicUsize::new(0),
+            live: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            baseline: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn reset(&self) {
+        let live = self.live.load(Relaxed);
+        self.allocations.store(0, Relaxed);
+        self.bytes.store(0, Relaxed);
+        self.peak.store(live, Relaxed);
+        self.baseline.store(live, Relaxed);
+    }
+
+    pub fn stats(&self) -> AllocStats {
+        AllocStats {
+            allocations: self.allocations.load(Relaxed),
+            bytes: self.bytes.load(Relaxed),
+            peak_bytes: self
+                .peak
+                .load(Relaxed)
+                .saturating_sub(self.baseline.load(Relaxed)),
+        }
+    }
+
+    fn allocated(&self, size: usize) {
+        self.allocations.fetch_add(1, Relaxed);
+        self.bytes.fetch_add(size, Relaxed);
+        let live = self.live.fetch_add(size, Relaxed) + size;
+        self.peak.fetch_max(live, Relaxed);
+    }
+
+    fn freed(&self, size: usize) {
+        self.live.fetch_sub(size, Relaxed);
+    }
+}
+
+impl Default for CountingAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.freed(layout.size());
+    }
+
+    // Growing a Vec or String counts as a new allocation of the new size, since that is what it usually costs
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.freed(layout.size());
+            self.allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn human_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    for unit in ["KiB", "MiB"] {
+        if value < 1024.0 {
+            return format!("{:.1} {}", value, unit);
+        }
+        value /= 1024.0;
+    }
+    format!("{:.1} GiB", value)
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.allocations,
+            human_bytes(self.bytes),
+            human_bytes(self.peak_bytes)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counting_allocator() {
+        let allocator = CountingAllocator::new();
+        let small = Layout::from_size_align(100, 8).unwrap();
+        let large = Layout::from_size_align(4000, 8).unwrap();
+        unsafe {
+            let a = allocator.alloc(small);
+            allocator.reset();
+            let b = allocator.alloc(large);
+            let b = allocator.realloc(b, large, 8000);
+            allocator.dealloc(b, Layout::from_size_align(8000, 8).unwrap());
+            let c = allocator.alloc_zeroed(small);
+            allocator.dealloc(c, small);
+            allocator.dealloc(a, small);
+        }
+
+        let stats = allocator.stats();
+        assert_eq!(
+            stats,
+            AllocStats {
+                allocations: 3,
+                bytes: 4000 + 8000 + 100,
+                peak_bytes: 8000,
+            }
+        );
+        assert_eq!(
+            stats.to_string(),
+            "3 allocs, 11.8 KiB allocated, 7.8 KiB peak"
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use std::fmt::{Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+
+const WORD_BITS: usize = 64;
+const INLINE_WORDS: usize = 2;
+
+#[derive(Clone)]
+enum Words {
+    // Up to 128 bits live on the stack, which covers most puzzle states without allocating
+    Inline([u64; INLINE_WORDS]),
+    Heap(Vec<u64>),
+}
+
+/// Set of indices `0..width`, stored as bits.
+#[derive(Clone)]
+pub struct BitSet {
+    width: usize,
+    words: Words,
+}
+
+impl BitSet {
+    pub fn new(width: usize) -> Self {
+        let nr_of_words = width.div_ceil(WORD_BITS);
+        let words = if nr_of_words <= INLINE_WORDS {
+            Words::Inline([0; INLINE_WORDS])
+        } else {
+            Words::Heap(vec![0; nr_of_words])
+        };
+        BitSet { width, words }
+    }
+
+    pub fn from_indices(width: usize, indices: impl IntoIterator<Item = usize>) -> Self {
+        let mut set = BitSet::new(width);
+        for i in indices {
+            set.insert(i);
+        }
+        set
+    }
+
+    /// Set with bit `i` set where `bits[i]` is true.
+    pub fn from_bools(bits: &[bool]) -> Self {
+        BitSet::from_indices(bits.len(), (0..bits.len()).filter(|&i| bits[i]))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    fn words(&self) -> &[u64] {
+        match &self.words {
+            Words::Inline(words) => words,
+            Words::Heap(words) => words,
+        }
+    }
+
+    fn words_mut(&mut self) -> &mut [u64] {
+        match &mut self.words {
+            Words::Inline(words) => words,
+            Words::Heap(words) => words,
+        }
+    }
+
+    fn position(&self, i: usize) -> (usize, u64) {
+        if i >= self.width {
+            panic!("Bit {i} out of range for BitSet of width {}", self.width);
+        }
+        (i / WORD_BITS, 1 << (i % WORD_BITS))
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        let (word, mask) = self.position(i);
+        self.words()[word] & mask != 0
+    }
+
+    /// Returns whether the bit was not set yet.
+    pub fn insert(&mut self, i: usize) -> bool {
+        let (word, mask) = self.position(i);
+        let word = &mut self.words_mut()[word];
+        let new = *word & mask == 0;
+        *word |= mask;
+        new
+    }
+
+    /// Returns whether the bit was set.
+    pub fn remove(&mut self, i: usize) -> bool {
+        let (word, mask) = self.position(i);
+        let word = &mut self.words_mut()[word];
+        let present = *word & mask != 0;
+        *word &= !mask;
+        present
+    }
+
+    pub fn toggle(&mut self, i: usize) {
+        let (word, mask) = self.position(i);
+        self.words_mut()[word] ^= mask;
+    }
+
+    pub fn clear(&mut self) {
+        self.words_mut().fill(0);
+    }
+
+    /// Nr of bits that are set.
+    pub fn count_ones(&self) -> usize {
+        self.words().iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words().iter().all(|&w| w == 0)
+    }
+
+    /// Indices of the bits that are set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words().iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                // Clear the lowest set bit
+                word &= word - 1;
+                Some(i * WORD_BITS + bit)
+            })
+        })
+    }
+
+    fn combine(&mut self, other: &BitSet, op: impl Fn(u64, u64) -> u64) {
+        if self.width != other.width {
+            panic!(
+                "Cannot combine BitSets of width {} and {}",
+                self.width, other.width
+            );
+        }
+        for (a, &b) in self.words_mut().iter_mut().zip(other.words()) {
+            *a = op(*a, b);
+        }
+    }
+}
+
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.words() == other.words()
+    }
+}
+
+impl Eq for BitSet {}
+
+impl Hash for BitSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.words().hash(state);
+    }
+}
+
+impl Debug for BitSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let bits: String = (0..self.width)
+            .map(|i| if self.contains(i) { '1' } else { '0' })
+            .collect();
+        write!(f, "BitSet({bits})")
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $op:tt) => {
+        impl $assign_trait<&BitSet> for BitSet {
+            fn $assign_method(&mut self, other: &BitSet) {
+                self.combine(other, |a, b| a $op b);
+            }
+        }
+
+        impl $trait<&BitSet> for &BitSet {
+            type Output = BitSet;
+
+            fn $method(self, other: &BitSet) -> BitSet {
+                let mut result = self.clone();
+                result.combine(other, |a, b| a $op b);
+                result
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+
+/// Dense set of visited grid positions, a drop-in for `HashSet<(usize, usize)>` on bounded grids.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct VisitedGrid {
+    width: usize,
+    height: usize,
+    bits: BitSet,
+}
+
+impl VisitedGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        VisitedGrid {
+            width,
+            height,
+            bits: BitSet::new(width * height),
+        }
+    }
+
+    fn index(&self, (x, y): (usize, usize)) -> usize {
+        if x >= self.width || y >= self.height {
+            panic!(
+                "Position ({x}, {y}) out of bounds for grid of {} x {}",
+                self.width, self.height
+            );
+        }
+        y * self.width + x
+    }
+
+    /// Returns whether the position was not visited yet.
+    pub fn insert(&mut self, position: (usize, usize)) -> bool {
+        let i = self.index(position);
+        self.bits.insert(i)
+    }
+
+    pub fn contains(&self, position: (usize, usize)) -> bool {
+        self.bits.contains(self.index(position))
+    }
+
+    /// Nr of visited positions.
+    pub fn len(&self) -> usize {
+        self.bits.count_ones()
+    }
+
+    /// Visited positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.bits.iter().map(|i| (i % self.width, i / self.width))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_bit_set() {
+        for width in [12, 128, 200] {
+            let mut a = BitSet::from_indices(width, [0, 3, 9]);
+            let b = BitSet::from_indices(width, [3, 4, width - 1]);
+            assert_eq!((&a ^ &b).iter().collect::<Vec<_>>(), [0, 4, 9, width - 1]);
+            assert_eq!((&a & &b).iter().collect::<Vec<_>>(), [3]);
+            assert_eq!((&a | &b).count_ones(), 5);
+
+            assert!(a.insert(width - 1));
+            assert!(!a.insert(width - 1));
+            assert!(a.remove(0));
+            a.toggle(4);
+            assert_eq!(a.iter().collect::<Vec<_>>(), [3, 4, 9, width - 1]);
+            a ^= &b;
+            assert_eq!(a.iter().collect::<Vec<_>>(), [9]);
+        }
+
+        // Usable as key for visited states
+        let seen: HashSet<BitSet> = [[1, 2], [2, 1], [1, 3]]
+            .into_iter()
+            .map(|indices| BitSet::from_indices(300, indices))
+            .collect();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(
+            BitSet::from_bools(&[false, true, true]),
+            BitSet::from_indices(3, [1, 2])
+        );
+    }
+
+    #[test]
+    fn test_visited_grid() {
+        let mut visited = VisitedGrid::new(3, 2);
+        assert!(visited.insert((2, 1)));
+        assert!(!visited.insert((2, 1)));
+        assert!(visited.insert((0, 1)));
+        assert!(visited.contains((0, 1)));
+        assert!(!visited.contains((1, 1)));
+        assert_eq!(visited.len(), 2);
+        assert_eq!(visited.iter().collect::<Vec<_>>(), [(0, 1), (2, 1)]);
+    }
+}
@@ -0,0 +1,164 @@
+/// Coordinate compression of one axis into cells.
+/// Every given coordinate gets a cell of width 1, and every gap between two consecutive given
+/// coordinates gets a single cell as well, so shapes keep their topology after compression.
+#[derive(Debug, Clone)]
+pub struct Axis {
+    // Cell i covers the original coordinates bounds[i]..bounds[i + 1]
+    bounds: Vec<i64>,
+}
+
+impl Axis {
+    pub fn new(coordinates: impl IntoIterator<Item = i64>) -> Self {
+        let mut bounds: Vec<i64> = coordinates.into_iter().flat_map(|c| [c, c + 1]).collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+        Axis { bounds }
+    }
+
+    /// Nr of cells on the axis.
+    pub fn len(&self) -> usize {
+        self.bounds.len().saturating_sub(1)
+    }
+
+    /// Index of the cell that contains the original coordinate.
+    pub fn index(&self, coordinate: i64) -> Option<usize> {
+        let i = self.bounds.partition_point(|&b| b <= coordinate);
+        (i > 0 && i < self.bounds.len()).then(|| i - 1)
+    }
+
+    /// First original coordinate in the cell.
+    pub fn coordinate(&self, index: usize) -> i64 {
+        self.bounds[index]
+    }
+
+    /// Nr of original coordinates in the cell.
+    pub fn width(&self, index: usize) -> i64 {
+        self.bounds[index + 1] - self.bounds[index]
+    }
+}
+
+/// Grid over two compressed axes, indexed as `[x][y]` like the grids of most days.
+#[derive(Debug, Clone)]
+pub struct CompressedGrid<V> {
+    pub x: Axis,
+    pub y: Axis,
+    cells: Vec<Vec<V>>,
+}
+
+impl<V: Clone> CompressedGrid<V> {
+    pub fn new(x: Axis, y: Axis, value: V) -> Self {
+        let cells = vec![vec![value; y.len()]; x.len()];
+        CompressedGrid { x, y, cells }
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [Vec<V>] {
+        &mut self.cells
+    }
+
+    /// Value of the cell that contains the original position.
+    pub fn get(&self, x: i64, y: i64) -> Option<&V> {
+        Some(&self.cells[self.x.index(x)?][self.y.index(y)?])
+    }
+
+    /// Set all cells that overlap the rectangle between the given original corners (inclusive).
+    pub fn fill(&mut self, from: (i64, i64), to: (i64, i64), value: V) {
+        let (xs, ys) = cell_ranges(&self.x, &self.y, from, to);
+        for x in xs {
+            for y in ys.clone() {
+                self.cells[x][y] = value.clone();
+            }
+        }
+    }
+
+    /// Summed-area table over the cells that match the predicate, to answer rectangle area queries in O(1).
+    pub fn area_table(&self, predicate: impl Fn(&V) -> bool) -> AreaTable {
+        let (nx, ny) = (self.x.len(), self.y.len());
+        let mut sums = vec![vec![0; ny + 1]; nx + 1];
+        for x in 0..nx {
+            for y in 0..ny {
+                let area = if predicate(&self.cells[x][y]) {
+                    self.x.width(x) * self.y.width(y)
+                } else {
+                    0
+                };
+                sums[x + 1][y + 1] = area + sums[x][y + 1] + sums[x + 1][y] - sums[x][y];
+            }
+        }
+        AreaTable {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            sums,
+        }
+    }
+}
+
+fn cell_ranges(
+    x: &Axis,
+    y: &Axis,
+    from: (i64, i64),
+    to: (i64, i64),
+) -> (
+    std::ops::RangeInclusive<usize>,
+    std::ops::RangeInclusive<usize>,
+) {
+    let index = |axis: &Axis, c: i64| axis.index(c).expect("Coordinate outside of the grid");
+    (
+        index(x, from.0.min(to.0))..=index(x, from.0.max(to.0)),
+        index(y, from.1.min(to.1))..=index(y, from.1.max(to.1)),
+    )
+}
+
+pub struct AreaTable {
+    x: Axis,
+    y: Axis,
+    sums: Vec<Vec<i64>>,
+}
+
+impl AreaTable {
+    /// Matching area of the whole grid.
+    pub fn total(&self) -> i64 {
+        self.sums[self.x.len()][self.y.len()]
+    }
+
+    /// Matching area of the cells that overlap the rectangle between the given original corners (inclusive).
+    /// Equals the area of the rectangle itself when it is fully covered, if its corners are on cell boundaries.
+    pub fn area(&self, from: (i64, i64), to: (i64, i64)) -> i64 {
+        let (xs, ys) = cell_ranges(&self.x, &self.y, from, to);
+        let (x1, x2, y1, y2) = (*xs.start(), *xs.end() + 1, *ys.start(), *ys.end() + 1);
+        self.sums[x2][y2] - self.sums[x1][y2] - self.sums[x2][y1] + self.sums[x1][y1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axis() {
+        let axis = Axis::new([10, 2, 5, 6]);
+        // Cells: [2], [3, 4], [5], [6], [7, 9], [10]
+        assert_eq!(axis.len(), 6);
+        assert_eq!(axis.index(3), Some(1));
+        assert_eq!(axis.index(4), Some(1));
+        assert_eq!(axis.index(10), Some(5));
+        assert_eq!(axis.index(1), None);
+        assert_eq!(axis.index(11), None);
+        assert_eq!(axis.coordinate(4), 7);
+        assert_eq!(axis.width(4), 3);
+    }
+
+    #[test]
+    fn test_grid_area() {
+        let mut grid =
+            CompressedGrid::new(Axis::new([0, 50, 100]), Axis::new([0, 500, 1000]), false);
+        grid.fill((0, 0), (100, 1000), true);
+        grid.fill((50, 500), (50, 500), false);
+        assert_eq!(grid.get(50, 500), Some(&false));
+        assert_eq!(grid.get(51, 500), Some(&true));
+
+        let table = grid.area_table(|&v| v);
+        assert_eq!(table.total(), 101 * 1001 - 1);
+        assert_eq!(table.area((0, 0), (100, 1000)), 101 * 1001 - 1);
+        assert_eq!(table.area((0, 0), (0, 1000)), 1001);
+    }
+}
@@ -0,0 +1,144 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+
+// Result of one implementation, a panic counts as an outcome of its own
+type Outcome<R> = Result<R, String>;
+
+fn run<T, R>(f: &impl Fn(&T) -> R, case: &T) -> Outcome<R> {
+    panic::catch_unwind(AssertUnwindSafe(|| f(case))).map_err(|e| {
+        e.downcast_ref::<String>()
+            .cloned()
+            .or_else(|| e.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+fn disagree<T, R: PartialEq>(
+    a: &impl Fn(&T) -> R,
+    b: &impl Fn(&T) -> R,
+    case: &T,
+) -> Option<(Outcome<R>, Outcome<R>)> {
+    let (result_a, result_b) = (run(a, case), run(b, case));
+    match (&result_a, &result_b) {
+        (Ok(x), Ok(y)) if x == y => None,
+        _ => Some((result_a, result_b)),
+    }
+}
+
+/// Check that two implementations give the same result on `cases` random inputs.
+/// Case `i` is generated from seed `i`, so failures are reproducible. A failing case is shrunk by
+/// repeatedly taking the first smaller candidate that still fails, and reported with both results.
+pub fn assert_agree<T: Debug, R: PartialEq + Debug>(
+    cases: u64,
+    generate: impl Fn(&mut StdRng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    a: impl Fn(&T) -> R,
+    b: impl Fn(&T) -> R,
+) {
+    for seed in 0..cases {
+        let case = generate(&mut StdRng::seed_from_u64(seed));
+        let Some(mut results) = disagree(&a, &b, &case) else {
+            continue;
+        };
+
+        let mut smallest = case;
+        let mut steps = 0;
+        while let Some((candidate, candidate_results)) = shrink(&smallest)
+            .into_iter()
+            .find_map(|c| disagree(&a, &b, &c).map(|r| (c, r)))
+        {
+            smallest = candidate;
+            results = candidate_results;
+            steps += 1;
+        }
+
+        panic!(
+            "Implementations disagree on case with seed {} (shrunk {} times):\n{:#?}\nfirst:  {:?}\nsecond: {:?}",
+            seed, steps, smallest, results.0, results.1
+        );
+    }
+}
+
+/// Smaller numbers to try, closest to 0 first.
+pub fn shrink_number(n: usize) -> Vec<usize> {
+    let mut candidates = Vec::new();
+    let mut delta = n;
+    while delta > 0 {
+        candidates.push(n - delta);
+        delta /= 2;
+    }
+    candidates
+}
+
+/// Shorter vecs first (without the first or second half, without one element), then vecs with one smaller element.
+pub fn shrink_vec<T: Clone>(v: &[T], shrink_item: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut candidates = Vec::new();
+    if v.len() > 1 {
+        candidates.push(v[v.len() / 2..].to_vec());
+        candidates.push(v[..v.len() / 2].to_vec());
+    }
+    for i in 0..v.len() {
+        candidates.push([&v[..i], &v[i + 1..]].concat());
+    }
+    for i in 0..v.len() {
+        for smaller in shrink_item(&v[i]) {
+            let mut candidate = v.to_vec();
+            candidate[i] = smaller;
+            candidates.push(candidate);
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_agree() {
+        assert_agree(
+            50,
+            |rng| {
+                (0..rng.gen_range(0..10))
+                    .map(|_| rng.gen_range(0..100))
+                    .collect::<Vec<usize>>()
+            },
+            |v| shrink_vec(v, |&n| shrink_number(n)),
+            |v| v.iter().sum::<usize>(),
+            |v| v.iter().rev().sum::<usize>(),
+        );
+    }
+
+    #[test]
+    fn test_shrinks_to_minimal_counterexample() {
+        let result = panic::catch_unwind(|| {
+            assert_agree(
+                50,
+                |rng| {
+                    (0..10)
+                        .map(|_| rng.gen_range(0..100))
+                        .collect::<Vec<usize>>()
+                },
+                |v| shrink_vec(v, |&n| shrink_number(n)),
+                |v| v.iter().sum::<usize>(),
+                // Wrong for values of 50 and more
+                |v| v.iter().map(|&n| n.min(50)).sum::<usize>(),
+            )
+        });
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("[\n    51,\n]"), "{message}");
+        assert!(
+            message.contains("first:  Ok(51)\nsecond: Ok(50)"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_shrink_number() {
+        assert_eq!(shrink_number(0), Vec::<usize>::new());
+        assert_eq!(shrink_number(10), [0, 5, 8, 9]);
+    }
+}
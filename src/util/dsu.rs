@@ -0,0 +1,144 @@
+use std::mem::swap;
+
+/// Disjoint-set/union-find (DSU) over the elements `0..n` - https://en.wikipedia.org/wiki/Disjoint-set_data_structure
+/// Uses path compression on `find` and union by size, so both operations are practically O(1).
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    // Each element points to its parent, roots point to themselves
+    parent: Vec<usize>,
+    // Size of the component, only valid for roots
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    pub fn new(n: usize) -> Self {
+        DisjointSet {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Root of the component that contains `x`.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Point every element on the path directly to the root
+        let mut current = x;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Merge the components of `a` and `b`. Returns false if they were already connected.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let mut root_a = self.find(a);
+        let mut root_b = self.find(b);
+        if root_a == root_b {
+            return false;
+        }
+
+        // Attach the smaller tree below the larger one to keep paths short
+        if self.size[root_a] < self.size[root_b] {
+            swap(&mut root_a, &mut root_b);
+        }
+        self.parent[root_b] = root_a;
+        self.size[root_a] += self.size[root_b];
+        self.components -= 1;
+        true
+    }
+
+    /// Make `x` a component of its own again. Only valid when every element of its component is reset,
+    /// so a large set can be reused after merging a few of its elements.
+    pub fn reset(&mut self, x: usize) {
+        if self.parent[x] != x {
+            self.parent[x] = x;
+            self.components += 1;
+        }
+        self.size[x] = 1;
+    }
+
+    /// Size of the component that contains `x`.
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Sizes of all components, largest first.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = (0..self.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|root| self.size[root])
+            .collect();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    /// Elements in the component that contains `x`, in increasing order.
+    pub fn members(&mut self, x: usize) -> impl Iterator<Item = usize> + '_ {
+        let root = self.find(x);
+        (0..self.len()).filter(move |&y| self.find(y) == root)
+    }
+
+    /// Elements of every component, grouped per component in order of their smallest element.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index_of_root = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        for x in 0..self.len() {
+            let root = self.find(x);
+            if index_of_root[root] == usize::MAX {
+                index_of_root[root] = components.len();
+                components.push(Vec::new());
+            }
+            components[index_of_root[root]].push(x);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut dsu = DisjointSet::new(8);
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(2, 3));
+        assert!(dsu.union(1, 3));
+        assert!(!dsu.union(0, 2));
+        assert!(dsu.union(5, 6));
+
+        assert_eq!(dsu.find(0), dsu.find(3));
+        assert_ne!(dsu.find(0), dsu.find(5));
+        assert_eq!(dsu.size_of(2), 4);
+        assert_eq!(dsu.component_count(), 4);
+        assert_eq!(dsu.component_sizes(), vec![4, 2, 1, 1]);
+        assert_eq!(dsu.members(6).collect::<Vec<_>>(), vec![5, 6]);
+        assert_eq!(
+            dsu.components(),
+            vec![vec![0, 1, 2, 3], vec![4], vec![5, 6], vec![7]]
+        );
+
+        for x in [0, 1, 2, 3] {
+            dsu.reset(x);
+        }
+        assert_eq!(dsu.component_count(), 7);
+        assert_eq!(dsu.component_sizes(), vec![2, 1, 1, 1, 1, 1, 1]);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::util::dsu::DisjointSet;
+
+// Side of the square blocks that points are counted in for the entropy
+const BLOCK: usize = 4;
+
+/// Measures of how structured a frame of a point simulation looks, e.g. robots that form a picture at one step.
+/// Frames of points spread at random all look alike, a frame with a pattern stands out on most of these.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameStats {
+    pub step: usize,
+    /// Shannon entropy in bits of the share of points per block of cells, low when points cluster.
+    pub entropy: f64,
+    /// Nr of occupied cells in the largest group of horizontally or vertically touching occupied cells.
+    pub largest_component: usize,
+    /// Sum of the variances of the x and the y coordinates, low when points are close together.
+    pub variance: f64,
+    /// Nr of runs of equal cells when reading the occupancy of the grid row by row,
+    /// low when the frame would compress well.
+    pub runs: usize,
+}
+
+/// Computes the `FrameStats` of the frames of a simulation on a `width` x `height` grid.
+/// Keeps its buffers between frames and only clears the cells a frame touched, since frames usually have
+/// far fewer points than the grid has cells.
+pub struct FrameScorer {
+    width: usize,
+    height: usize,
+    occupied: Vec<bool>,
+    // Occupied cells of the current frame, each one once
+    cells: Vec<usize>,
+    // Nr of points per block of cells, and the blocks that have any
+    blocks: Vec<usize>,
+    touched_blocks: Vec<usize>,
+    groups: DisjointSet,
+}
+
+impl FrameScorer {
+    pub fn new(width: usize, height: usize) -> Self {
+        FrameScorer {
+            width,
+            height,
+            occupied: vec![false; width * height],
+            cells: Vec::new(),
+            blocks: vec![0; width.div_ceil(BLOCK) * height.div_ceil(BLOCK)],
+            touched_blocks: Vec::new(),
+            groups: DisjointSet::new(width * height),
+        }
+    }
+
+    /// Stats of the points at a step of the simulation, points are (x, y) with x < width.
+    /// Points on the same cell count once for the component and the runs, but every one of them counts for the rest.
+    pub fn stats(&mut self, step: usize, points: &[(usize, usize)]) -> FrameStats {
+        let height = self.height;
+        let blocks_per_row = height.div_ceil(BLOCK);
+        for &(x, y) in points {
+            let i = x * height + y;
+            if !self.occupied[i] {
+                self.occupied[i] = true;
+                self.cells.push(i);
+            }
+            let block = x / BLOCK * blocks_per_row + y / BLOCK;
+            if self.blocks[block] == 0 {
+                self.touched_blocks.push(block);
+            }
+            self.blocks[block] += 1;
+        }
+
+        let n = points.len().max(1) as f64;
+        let entropy = -self
+            .touched_blocks
+            .iter()
+            .map(|&block| self.blocks[block] as f64 / n)
+            .map(|p| p * p.log2())
+            .sum::<f64>();
+
+        let variance_of = |coordinate: fn(&(usize, usize)) -> usize| {
+            let mean = points.iter().map(|p| coordinate(p) as f64).sum::<f64>() / n;
+            points
+                .iter()
+                .map(|p| (coordinate(p) as f64 - mean).powi(2))
+                .sum::<f64>()
+                / n
+        };
+        let variance = variance_of(|p| p.0) + variance_of(|p| p.1);
+
+        let occupied = &self.occupied;
+        let size = self.width * height;
+        for &i in &self.cells {
+            if (i + 1) % height != 0 && occupied[i + 1] {
+                self.groups.union(i, i + 1);
+            }
+            if i + height < size && occupied[i + height] {
+                self.groups.union(i, i + height);
+            }
+        }
+        let largest_component = self
+            .cells
+            .iter()
+            .map(|&i| self.groups.size_of(i))
+            .max()
+            .unwrap_or(0);
+
+        // A run starts at every change from empty to occupied or back
+        let changes: usize = self
+            .cells
+            .iter()
+            .map(|&i| {
+                usize::from(i > 0 && !occupied[i - 1])
+                    + usize::from(i + 1 < size && !occupied[i + 1])
+            })
+            .sum();
+        let runs = 1 + changes;
+
+        // Components only contain occupied cells, so resetting those leaves the whole set as new
+        for &i in &self.cells {
+            self.occupied[i] = false;
+            self.groups.reset(i);
+        }
+        self.cells.clear();
+        for &block in &self.touched_blocks {
+            self.blocks[block] = 0;
+        }
+        self.touched_blocks.clear();
+
+        FrameStats {
+            step,
+            entropy,
+            largest_component,
+            variance,
+            runs,
+        }
+    }
+}
+
+// Nr of standard deviations that every value lies from the mean of all values, 0 when they are all equal
+fn z_scores(values: &[f64]) -> Vec<f64> {
+    let n = values.len().max(1) as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let deviation = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    values
+        .iter()
+        .map(|v| {
+            if deviation > 0.0 {
+                (v - mean) / deviation
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Frames ordered from most to least interesting, together with their score.
+/// The score adds up how many standard deviations a frame lies from the average frame on every measure,
+/// counted in the direction of more structure. No single measure or threshold has to fit the input that way.
+pub fn rank(frames: &[FrameStats]) -> Vec<(f64, &FrameStats)> {
+    let measures = [
+        z_scores(&frames.iter().map(|f| -f.entropy).collect::<Vec<f64>>()),
+        z_scores(
+            &frames
+                .iter()
+                .map(|f| f.largest_component as f64)
+                .collect::<Vec<f64>>(),
+        ),
+        z_scores(&frames.iter().map(|f| -f.variance).collect::<Vec<f64>>()),
+        z_scores(
+            &frames
+                .iter()
+                .map(|f| -(f.runs as f64))
+                .collect::<Vec<f64>>(),
+        ),
+    ];
+    let mut ranked: Vec<(f64, &FrameStats)> = frames
+        .iter()
+        .enumerate()
+        .map(|(i, frame)| (measures.iter().map(|m| m[i]).sum(), frame))
+        .collect();
+    ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+    ranked
+}
+
+/// Table of the ranked frames, to print or write next to the images of the frames.
+pub fn table(ranked: &[(f64, &FrameStats)]) -> String {
+    let mut lines = vec![format!(
+        "{:>6} {:>8} {:>8} {:>9} {:>10} {:>6}",
+        "step", "score", "entropy", "component", "variance", "runs"
+    )];
+    for (score, frame) in ranked {
+        lines.push(format!(
+            "{:>6} {:>8.2} {:>8.3} {:>9} {:>10.1} {:>6}",
+            frame.step, score, frame.entropy, frame.largest_component, frame.variance, frame.runs
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_stats() {
+        // Filled 2 x 2 square in a corner, with a point on top of another one
+        let square = [(0, 0), (0, 1), (1, 0), (1, 1), (1, 1)];
+        let mut scorer = FrameScorer::new(8, 8);
+        let stats = scorer.stats(3, &square);
+        assert_eq!(stats.step, 3);
+        assert_eq!(stats.entropy, 0.0);
+        assert_eq!(stats.largest_component, 4);
+        // Rows 0 and 1 start with 2 occupied cells
+        assert_eq!(stats.runs, 4);
+
+        // Nothing of the previous frame is left when scoring the next one
+        let line = [(2, 2), (2, 3), (2, 4)];
+        assert_eq!(
+            scorer.stats(4, &line),
+            FrameScorer::new(8, 8).stats(4, &line)
+        );
+        assert_eq!(scorer.stats(3, &square), stats);
+
+        let scattered = [(0, 0), (0, 11), (11, 0), (11, 11), (5, 5)];
+        let stats = FrameScorer::new(12, 12).stats(4, &scattered);
+        assert!((stats.entropy - 5f64.log2()).abs() < 1e-9);
+        assert_eq!(stats.largest_component, 1);
+        assert!(stats.variance > scorer.stats(3, &square).variance);
+    }
+
+    #[test]
+    fn test_rank() {
+        let scattered =
+            |offset: usize| [(offset, 0), (0, 7 - offset), (7, offset), (7 - offset, 7)];
+        let mut scorer = FrameScorer::new(8, 8);
+        let frames = vec![
+            scorer.stats(0, &scattered(0)),
+            scorer.stats(1, &scattered(1)),
+            scorer.stats(2, &[(3, 3), (3, 4), (4, 3), (4, 4)]),
+            scorer.stats(3, &scattered(2)),
+        ];
+        let ranked = rank(&frames);
+        assert_eq!(ranked[0].1.step, 2);
+        assert!(ranked[0].0 > 0.0);
+        assert_eq!(table(&ranked[..1]).lines().count(), 2);
+    }
+}
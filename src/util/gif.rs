@@ -0,0 +1,165 @@
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, ImageResult, Rgb, RgbImage};
+use std::collections::HashMap;
+use std::io::Write;
+
+/// Colors of the characters of a grid, characters without a color get the background color.
+#[derive(Debug, Clone)]
+pub struct Palette {
+    colors: HashMap<char, Rgb<u8>>,
+    background: Rgb<u8>,
+}
+
+impl Palette {
+    pub fn new(colors: &[(char, [u8; 3])]) -> Self {
+        Palette {
+            colors: colors.iter().map(|&(c, rgb)| (c, Rgb(rgb))).collect(),
+            background: Rgb([0, 0, 0]),
+        }
+    }
+
+    pub fn background(mut self, rgb: [u8; 3]) -> Self {
+        self.background = Rgb(rgb);
+        self
+    }
+
+    pub fn color(&self, c: char) -> Rgb<u8> {
+        self.get(c).unwrap_or(self.background)
+    }
+
+    /// Color of the character, if it has one.
+    pub fn get(&self, c: char) -> Option<Rgb<u8>> {
+        self.colors.get(&c).copied()
+    }
+}
+
+/// Image of a grid given as lines of characters, every cell is a square of `cell_size` pixels.
+pub fn render_grid(grid: &str, palette: &Palette, cell_size: u32) -> RgbImage {
+    let width = grid.lines().map(|l| l.chars().count()).max().unwrap_or(0) as u32;
+    let height = grid.lines().count() as u32;
+    let mut img = RgbImage::from_pixel(width * cell_size, height * cell_size, palette.background);
+    for (y, line) in grid.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            let color = palette.color(c);
+            for dy in 0..cell_size {
+                for dx in 0..cell_size {
+                    img.put_pixel(x as u32 * cell_size + dx, y as u32 * cell_size + dy, color);
+                }
+            }
+        }
+    }
+    img
+}
+
+/// Records successive states of a grid simulation, to write them as an animated GIF.
+/// Frames are kept as text and only turned into pixels when encoding, so long simulations stay cheap.
+#[derive(Debug, Clone)]
+pub struct GifRecorder {
+    palette: Palette,
+    cell_size: u32,
+    delay_ms: u32,
+    every: usize,
+    offered: usize,
+    frames: Vec<String>,
+}
+
+impl GifRecorder {
+    pub fn new(palette: Palette) -> Self {
+        GifRecorder {
+            palette,
+            cell_size: 4,
+            delay_ms: 100,
+            every: 1,
+            offered: 0,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Nr of pixels per side of a cell.
+    pub fn cell_size(mut self, cell_size: u32) -> Self {
+        self.cell_size = cell_size.max(1);
+        self
+    }
+
+    /// Time every frame is shown. GIFs count in steps of 10 ms.
+    pub fn delay_ms(mut self, delay_ms: u32) -> Self {
+        self.delay_ms = delay_ms;
+        self
+    }
+
+    /// Keep only every n-th recorded state, for simulations with too many steps to watch.
+    pub fn every(mut self, n: usize) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    /// Record the next state, `grid` is only called to draw it for the states that are kept.
+    pub fn record_with(&mut self, grid: impl FnOnce() -> String) {
+        if self.offered.is_multiple_of(self.every) {
+            self.frames.push(grid());
+        }
+        self.offered += 1;
+    }
+
+    /// Record the end state even if it would be skipped, so the animation ends on the result.
+    pub fn record_final(&mut self, grid: &str) {
+        if self.frames.last().map(String::as_str) != Some(grid) {
+            self.frames.push(grid.to_string());
+        }
+    }
+
+    /// Write the frames as a looping GIF.
+    pub fn encode(&self, writer: impl Write) -> ImageResult<()> {
+        // Palettes are small enough for GIFs to hold them exactly, so the quality setting does not matter
+        let mut encoder = GifEncoder::new_with_speed(writer, 30);
+        encoder.set_repeat(Repeat::Infinite)?;
+        let delay = Delay::from_numer_denom_ms(self.delay_ms, 1);
+        for grid in &self.frames {
+            let img = render_grid(grid, &self.palette, self.cell_size);
+            let rgba = image::DynamicImage::ImageRgb8(img).into_rgba8();
+            encoder.encode_frame(Frame::from_parts(rgba, 0, 0, delay))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::gif::GifDecoder;
+    use image::AnimationDecoder;
+
+    #[test]
+    fn test_gif_recorder() {
+        let palette = Palette::new(&[('#', [255, 255, 255]), ('@', [255, 0, 0])]);
+        let mut recorder = GifRecorder::new(palette).cell_size(2).delay_ms(50).every(2);
+        let mut drawn = 0;
+        for grid in ["#@.\n#..", "#.@\n#..", "#..\n#.@", "#..\n#@."] {
+            recorder.record_with(|| {
+                drawn += 1;
+                grid.to_string()
+            });
+        }
+        // Skipped states are not drawn at all
+        assert_eq!(drawn, 2);
+        assert_eq!(recorder.frames.len(), 2);
+        recorder.record_final("#..\n#@.");
+        recorder.record_final("#..\n#@.");
+        assert_eq!(recorder.frames.len(), 3);
+
+        let mut bytes = Vec::new();
+        recorder.encode(&mut bytes).unwrap();
+        let frames = GifDecoder::new(bytes.as_slice())
+            .unwrap()
+            .into_frames()
+            .collect_frames()
+            .unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].delay().numer_denom_ms(), (50, 1));
+        let last = frames[2].buffer();
+        assert_eq!(last.dimensions(), (6, 4));
+        assert_eq!(last.get_pixel(0, 3).0, [255, 255, 255, 255]);
+        assert_eq!(last.get_pixel(3, 3).0, [255, 0, 0, 255]);
+        assert_eq!(last.get_pixel(5, 0).0, [0, 0, 0, 255]);
+    }
+}
@@ -0,0 +1,379 @@
+use crate::util::dsu::DisjointSet;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Adjacency-list graph over nodes with string names.
+/// Names are interned once, after which nodes are referred to by their `usize` id.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    directed: bool,
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl Graph {
+    pub fn undirected() -> Self {
+        Graph::default()
+    }
+
+    pub fn directed() -> Self {
+        Graph {
+            directed: true,
+            ..Graph::default()
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Id of the node with the given name, adding the node if it does not exist yet.
+    pub fn add_node(&mut self, name: &str) -> usize {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.adjacency.push(Vec::new());
+        id
+    }
+
+    /// Add an edge between the named nodes. Adding the same edge twice results in a multi-edge.
+    pub fn add_edge(&mut self, from: &str, to: &str) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.adjacency[from].push(to);
+        if !self.directed && from != to {
+            self.adjacency[to].push(from);
+        }
+    }
+
+    /// Id of the node with the given name, if there is one.
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    pub fn neighbors(&self, id: usize) -> &[usize] {
+        &self.adjacency[id]
+    }
+
+    /// Every edge once, also for undirected graphs.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(from, targets)| targets.iter().map(move |&to| (from, to)))
+            .filter(|(from, to)| self.directed || from <= to)
+    }
+
+    pub fn to_dot(&self) -> String {
+        let (kind, connector) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let quote = |id: usize| format!("\"{}\"", self.names[id].replace('"', "\\\""));
+
+        let mut dot_content = format!("{} {{\n", kind);
+        for (from, to) in self.edges() {
+            dot_content.push_str(&format!("  {} {} {};\n", quote(from), connector, quote(to)));
+        }
+        // Nodes without any edges would not show up otherwise
+        for id in (0..self.len()).filter(|&id| self.adjacency[id].is_empty()) {
+            dot_content.push_str(&format!("  {};\n", quote(id)));
+        }
+        dot_content.push_str("}\n");
+        dot_content
+    }
+
+    /// Weakly connected components, each sorted by node id.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut dsu = DisjointSet::new(self.len());
+        self.edges().for_each(|(from, to)| {
+            dsu.union(from, to);
+        });
+        dsu.components()
+    }
+
+    /// All maximal cliques of an undirected graph - https://en.wikipedia.org/wiki/Bron%E2%80%93Kerbosch_algorithm
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        // Bron–Kerbosch would report the empty clique
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let neighbors: Vec<HashSet<usize>> = self
+            .adjacency
+            .iter()
+            .enumerate()
+            .map(|(id, targets)| targets.iter().copied().filter(|&t| t != id).collect())
+            .collect();
+        let mut cliques = Vec::new();
+        bron_kerbosch(
+            &neighbors,
+            &mut Vec::new(),
+            (0..self.len()).collect(),
+            Vec::new(),
+            &mut cliques,
+        );
+        cliques
+            .iter_mut()
+            .for_each(|clique: &mut Vec<usize>| clique.sort_unstable());
+        cliques
+    }
+
+    /// Minimum cut of an undirected graph with unit edge weights - https://en.wikipedia.org/wiki/Stoer%E2%80%93Wagner_algorithm
+    /// Returns the nr of edges crossing the cut and the nodes on one side of it.
+    pub fn min_cut(&self) -> Option<(usize, Vec<usize>)> {
+        let n = self.len();
+        if n < 2 {
+            return None;
+        }
+
+        // Weights between merged nodes, and which original nodes were merged into each node
+        let mut weights: Vec<HashMap<usize, usize>> = vec![HashMap::new(); n];
+        for (from, to) in self.edges().filter(|(from, to)| from != to) {
+            *weights[from].entry(to).or_default() += 1;
+            *weights[to].entry(from).or_default() += 1;
+        }
+        let mut merged: Vec<Vec<usize>> = (0..n).map(|id| vec![id]).collect();
+        let mut active: Vec<bool> = vec![true; n];
+        let mut best: Option<(usize, Vec<usize>)> = None;
+
+        for phase in 0..n - 1 {
+            // Maximum adjacency search: keep adding the node most tightly connected to the added set
+            let start = active.iter().position(|&a| a).unwrap();
+            let mut added = vec![false; n];
+            let mut connectivity = vec![0; n];
+            let mut heap = BinaryHeap::from([(0, start)]);
+            let (mut s, mut t) = (start, start);
+            let mut nr_added = 0;
+            while let Some((weight, v)) = heap.pop() {
+                if added[v] || weight != connectivity[v] {
+                    continue;
+                }
+                added[v] = true;
+                nr_added += 1;
+                (s, t) = (t, v);
+                for (&u, &w) in &weights[v] {
+                    if !added[u] {
+                        connectivity[u] += w;
+                        heap.push((connectivity[u], u));
+                    }
+                }
+            }
+
+            // Graph is disconnected: the nodes that were reached form a cut without edges
+            if nr_added < n - phase {
+                let side = (0..n)
+                    .filter(|&v| added[v])
+                    .flat_map(|v| merged[v].clone())
+                    .collect();
+                return Some((0, side));
+            }
+
+            // Cut of the phase separates the last added node from all others
+            if best.as_ref().is_none_or(|(cut, _)| connectivity[t] < *cut) {
+                best = Some((connectivity[t], merged[t].clone()));
+            }
+
+            // Merge the last two added nodes
+            let merged_t = std::mem::take(&mut merged[t]);
+            merged[s].extend(merged_t);
+            for (u, w) in std::mem::take(&mut weights[t]) {
+                weights[u].remove(&t);
+                if u != s {
+                    *weights[s].entry(u).or_default() += w;
+                    *weights[u].entry(s).or_default() += w;
+                }
+            }
+            active[t] = false;
+        }
+
+        best.map(|(cut, mut side)| {
+            side.sort_unstable();
+            (cut, side)
+        })
+    }
+
+    /// Topological order of a directed graph (Kahn's algorithm).
+    /// Returns None if the graph has a cycle, or is undirected since every edge goes both ways there.
+    pub fn topological_sort(&self) -> Option<Vec<usize>> {
+        if !self.directed {
+            return None;
+        }
+        let mut in_degree = vec![0; self.len()];
+        self.edges().for_each(|(_, to)| in_degree[to] += 1);
+
+        let mut ready: Vec<usize> = (0..self.len()).filter(|&v| in_degree[v] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(v) = ready.pop() {
+            order.push(v);
+            for &next in &self.adjacency[v] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.push(next);
+                }
+            }
+        }
+
+        (order.len() == self.len()).then_some(order)
+    }
+
+    /// Nr of distinct paths from `source` to `target` in a directed acyclic graph.
+    /// Returns None if the graph has a cycle.
+    pub fn count_paths(&self, source: usize, target: usize) -> Option<usize> {
+        let order = self.topological_sort()?;
+        let mut paths = vec![0; self.len()];
+        paths[source] = 1;
+        for v in order {
+            if paths[v] == 0 {
+                continue;
+            }
+            for &next in &self.adjacency[v] {
+                paths[next] += paths[v];
+            }
+        }
+        Some(paths[target])
+    }
+}
+
+// Bron–Kerbosch with pivoting: `clique` is the clique being grown, `candidates` can still extend it and
+// `excluded` were already tried, so cliques containing them have been reported before
+fn bron_kerbosch(
+    neighbors: &[HashSet<usize>],
+    clique: &mut Vec<usize>,
+    mut candidates: Vec<usize>,
+    mut excluded: Vec<usize>,
+    cliques: &mut Vec<Vec<usize>>,
+) {
+    if candidates.is_empty() && excluded.is_empty() {
+        cliques.push(clique.clone());
+        return;
+    }
+
+    // Any maximal clique contains the pivot or one of its non-neighbors
+    let pivot = *candidates
+        .iter()
+        .chain(excluded.iter())
+        .max_by_key(|&&u| neighbors[u].len())
+        .unwrap();
+    let to_try: Vec<usize> = candidates
+        .iter()
+        .copied()
+        .filter(|v| !neighbors[pivot].contains(v))
+        .collect();
+
+    for v in to_try {
+        clique.push(v);
+        bron_kerbosch(
+            neighbors,
+            clique,
+            candidates
+                .iter()
+                .copied()
+                .filter(|u| neighbors[v].contains(u))
+                .collect(),
+            excluded
+                .iter()
+                .copied()
+                .filter(|u| neighbors[v].contains(u))
+                .collect(),
+            cliques,
+        );
+        clique.pop();
+        candidates.retain(|&u| u != v);
+        excluded.push(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn undirected(edges: &[(&str, &str)]) -> Graph {
+        let mut graph = Graph::undirected();
+        edges.iter().for_each(|(from, to)| graph.add_edge(from, to));
+        graph
+    }
+
+    #[test]
+    fn test_dot_and_components() {
+        let mut graph = undirected(&[("a", "b"), ("b", "c"), ("d", "e")]);
+        graph.add_node("f");
+        assert_eq!(
+            graph.to_dot(),
+            "graph {\n  \"a\" -- \"b\";\n  \"b\" -- \"c\";\n  \"d\" -- \"e\";\n  \"f\";\n}\n"
+        );
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec![0, 1, 2], vec![3, 4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn test_maximal_cliques() {
+        let graph = undirected(&[
+            ("a", "b"),
+            ("a", "c"),
+            ("b", "c"),
+            ("c", "d"),
+            ("d", "e"),
+            ("c", "e"),
+            ("e", "f"),
+        ]);
+        let mut cliques = graph.maximal_cliques();
+        cliques.sort();
+        assert_eq!(cliques, vec![vec![0, 1, 2], vec![2, 3, 4], vec![4, 5]]);
+        assert!(Graph::undirected().maximal_cliques().is_empty());
+    }
+
+    #[test]
+    fn test_min_cut() {
+        // Two triangles connected by a single edge
+        let graph = undirected(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("d", "e"),
+            ("e", "f"),
+            ("f", "d"),
+            ("c", "d"),
+        ]);
+        let (cut, side) = graph.min_cut().unwrap();
+        assert_eq!(cut, 1);
+        assert!(side == vec![0, 1, 2] || side == vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_topological_sort_and_count_paths() {
+        let mut graph = Graph::directed();
+        [("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("d", "e")]
+            .iter()
+            .for_each(|(from, to)| graph.add_edge(from, to));
+        let order = graph.topological_sort().unwrap();
+        let position = |name| order.iter().position(|&v| v == graph.id(name).unwrap());
+        assert!(position("a") < position("b") && position("b") < position("d"));
+        assert_eq!(graph.count_paths(0, graph.id("e").unwrap()), Some(2));
+
+        graph.add_edge("e", "a");
+        assert_eq!(graph.topological_sort(), None);
+
+        // Every edge of an undirected graph is a cycle of its own
+        let graph = undirected(&[("a", "b"), ("b", "c")]);
+        assert_eq!(graph.topological_sort(), None);
+        assert_eq!(graph.count_paths(0, 2), None);
+    }
+}
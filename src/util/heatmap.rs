@@ -0,0 +1,107 @@
+use image::{Rgb, RgbImage};
+
+// Color stops from low to high values, perceptually close to viridis
+const GRADIENT: [[u8; 3]; 5] = [
+    [68, 1, 84],
+    [59, 82, 139],
+    [33, 145, 140],
+    [94, 201, 98],
+    [253, 231, 37],
+];
+const UNREACHED: Rgb<u8> = Rgb([25, 25, 25]);
+const PATH: Rgb<u8> = Rgb([255, 40, 40]);
+
+/// Color of a value between 0 (lowest) and 1 (highest).
+pub fn gradient(t: f64) -> Rgb<u8> {
+    let t = t.clamp(0.0, 1.0) * (GRADIENT.len() - 1) as f64;
+    let i = (t.floor() as usize).min(GRADIENT.len() - 2);
+    let f = t - i as f64;
+    let (a, b) = (GRADIENT[i], GRADIENT[i + 1]);
+    Rgb([0, 1, 2].map(|c| (a[c] as f64 + (b[c] as f64 - a[c] as f64) * f).round() as u8))
+}
+
+/// Best cost per cell of a grid, e.g. from a shortest path search, to draw as a heatmap.
+/// Cells are indexed row by row, cells that were never reached stay empty.
+#[derive(Debug, Clone)]
+pub struct Heatmap {
+    width: usize,
+    height: usize,
+    costs: Vec<Option<usize>>,
+}
+
+impl Heatmap {
+    pub fn new(width: usize, height: usize) -> Self {
+        Heatmap {
+            width,
+            height,
+            costs: vec![None; width * height],
+        }
+    }
+
+    /// Keeps the lowest cost seen for the cell.
+    pub fn record(&mut self, index: usize, cost: usize) {
+        let current = &mut self.costs[index];
+        if current.is_none_or(|c| cost < c) {
+            *current = Some(cost);
+        }
+    }
+
+    /// Cells colored from dark (cheap) to bright (expensive), with the cells of `path` drawn on top in red.
+    pub fn render(&self, path: &[usize], cell_size: u32) -> RgbImage {
+        let max = self
+            .costs
+            .iter()
+            .flatten()
+            .max()
+            .copied()
+            .unwrap_or(0)
+            .max(1);
+        let mut img = RgbImage::new(
+            self.width as u32 * cell_size,
+            self.height as u32 * cell_size,
+        );
+        let mut fill = |index: usize, color: Rgb<u8>| {
+            let (x, y) = ((index % self.width) as u32, (index / self.width) as u32);
+            for dy in 0..cell_size {
+                for dx in 0..cell_size {
+                    img.put_pixel(x * cell_size + dx, y * cell_size + dy, color);
+                }
+            }
+        };
+        for (index, cost) in self.costs.iter().enumerate() {
+            fill(
+                index,
+                cost.map_or(UNREACHED, |c| gradient(c as f64 / max as f64)),
+            );
+        }
+        for &index in path {
+            fill(index, PATH);
+        }
+        img
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heatmap() {
+        let mut heatmap = Heatmap::new(3, 2);
+        heatmap.record(0, 5);
+        heatmap.record(0, 0);
+        heatmap.record(0, 3);
+        heatmap.record(2, 10);
+        heatmap.record(4, 4);
+        assert_eq!(heatmap.costs[0], Some(0));
+        assert_eq!(heatmap.costs[1], None);
+
+        let img = heatmap.render(&[4], 2);
+        assert_eq!(img.dimensions(), (6, 4));
+        assert_eq!(*img.get_pixel(0, 0), Rgb(GRADIENT[0]));
+        assert_eq!(*img.get_pixel(3, 1), UNREACHED);
+        assert_eq!(*img.get_pixel(5, 0), Rgb(GRADIENT[4]));
+        assert_eq!(*img.get_pixel(3, 3), PATH);
+        assert_eq!(gradient(0.5), Rgb(GRADIENT[2]));
+    }
+}
@@ -0,0 +1,245 @@
+use crate::util::graph::Graph;
+use crate::util::svg::{Point, Svg};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet};
+
+/// Colors of node groups, groups past the end wrap around.
+const GROUP_COLORS: [&str; 8] = [
+    "#4e79a7", "#f28e2b", "#59a14f", "#e15759", "#b07aa1", "#76b7b2", "#edc948", "#9c755f",
+];
+const EDGE_COLOR: &str = "#bbbbbb";
+const HIGHLIGHT_COLOR: &str = "#d62728";
+
+/// Force-directed layout - https://en.wikipedia.org/wiki/Force-directed_graph_drawing
+/// Fruchterman-Reingold: edges pull their nodes together, nodes close to each other push apart,
+/// and the distance nodes may move shrinks every iteration until the layout settles.
+/// Repulsion only looks at nodes in neighboring grid cells, so large puzzle graphs stay fast.
+/// Returns positions within the unit square, the same ones for the same seed.
+pub fn force_directed(graph: &Graph, iterations: usize, seed: u64) -> Vec<Point> {
+    let n = graph.len();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut positions: Vec<Point> = (0..n).map(|_| (rng.gen(), rng.gen())).collect();
+    if n < 2 {
+        return positions;
+    }
+
+    // Ideal distance between nodes when they spread evenly over the unit square
+    let k = (1.0 / n as f64).sqrt();
+    let cell_size = 2.0 * k;
+    let edges: Vec<(usize, usize)> = graph.edges().filter(|(a, b)| a != b).collect();
+    let start_temperature = 0.1;
+
+    for iteration in 0..iterations {
+        let mut displacement: Vec<Point> = vec![(0.0, 0.0); n];
+
+        let cell = |(x, y): Point| {
+            (
+                (x / cell_size).floor() as i64,
+                (y / cell_size).floor() as i64,
+            )
+        };
+        let mut cells: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (v, &p) in positions.iter().enumerate() {
+            cells.entry(cell(p)).or_default().push(v);
+        }
+        for v in 0..n {
+            let (cx, cy) = cell(positions[v]);
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    for &u in cells.get(&(cx + dx, cy + dy)).into_iter().flatten() {
+                        if u == v {
+                            continue;
+                        }
+                        let (mut x, mut y) = (
+                            positions[v].0 - positions[u].0,
+                            positions[v].1 - positions[u].1,
+                        );
+                        // Nodes on top of each other get pushed apart in a direction based on their ids
+                        if x == 0.0 && y == 0.0 {
+                            (x, y) = ((v as f64 - u as f64) * 1e-6, 1e-6);
+                        }
+                        let distance = (x * x + y * y).sqrt();
+                        if distance < cell_size {
+                            let force = k * k / distance;
+                            displacement[v].0 += x / distance * force;
+                            displacement[v].1 += y / distance * force;
+                        }
+                    }
+                }
+            }
+        }
+
+        for &(a, b) in &edges {
+            let (x, y) = (
+                positions[a].0 - positions[b].0,
+                positions[a].1 - positions[b].1,
+            );
+            let distance = (x * x + y * y).sqrt().max(1e-9);
+            let force = distance * distance / k;
+            displacement[a].0 -= x / distance * force;
+            displacement[a].1 -= y / distance * force;
+            displacement[b].0 += x / distance * force;
+            displacement[b].1 += y / distance * force;
+        }
+
+        // Linear cooling, the last iteration still moves a little
+        let temperature = start_temperature * (iterations - iteration) as f64 / iterations as f64;
+        for (p, (dx, dy)) in positions.iter_mut().zip(displacement) {
+            let length = (dx * dx + dy * dy).sqrt();
+            if length > 0.0 {
+                let step = length.min(temperature);
+                p.0 += dx / length * step;
+                p.1 += dy / length * step;
+            }
+        }
+    }
+
+    // Stretch the layout back over the unit square, centered along its shorter side
+    let (x_min, x_max, y_min, y_max) = positions.iter().fold(
+        (f64::MAX, f64::MIN, f64::MAX, f64::MIN),
+        |(x_min, x_max, y_min, y_max), &(x, y)| {
+            (x_min.min(x), x_max.max(x), y_min.min(y), y_max.max(y))
+        },
+    );
+    let scale = (x_max - x_min).max(y_max - y_min).max(f64::EPSILON);
+    let (x_offset, y_offset) = (
+        (1.0 - (x_max - x_min) / scale) / 2.0,
+        (1.0 - (y_max - y_min) / scale) / 2.0,
+    );
+    positions
+        .into_iter()
+        .map(|(x, y)| {
+            (
+                (x - x_min) / scale + x_offset,
+                (y - y_min) / scale + y_offset,
+            )
+        })
+        .collect()
+}
+
+/// How to draw a graph: group per node to color it by, edges to draw in a different color.
+#[derive(Debug, Clone, Default)]
+pub struct GraphStyle {
+    /// Group of every node by id, nodes without a group use the first color
+    pub groups: Vec<usize>,
+    /// Edges as (from, to) ids, for undirected graphs either direction matches
+    pub highlighted_edges: HashSet<(usize, usize)>,
+    /// Write node names next to the nodes, only readable for small graphs
+    pub labels: bool,
+}
+
+impl GraphStyle {
+    fn is_highlighted(&self, graph: &Graph, from: usize, to: usize) -> bool {
+        self.highlighted_edges.contains(&(from, to))
+            || (!graph.is_directed() && self.highlighted_edges.contains(&(to, from)))
+    }
+}
+
+/// Draw the graph at the given positions in the unit square, see `force_directed`, on a square of `size` pixels.
+/// Hovering over a node shows its name, also without labels.
+pub fn graph_to_svg(graph: &Graph, positions: &[Point], style: &GraphStyle, size: f64) -> Svg {
+    let margin = 20.0;
+    let radius = (size / (graph.len().max(1) as f64).sqrt() / 8.0).clamp(2.0, 10.0);
+    let to_pixel = |(x, y): Point| {
+        (
+            margin + x * (size - 2.0 * margin),
+            margin + y * (size - 2.0 * margin),
+        )
+    };
+
+    let mut svg = Svg::new(size, size);
+    svg.background("white");
+    // Highlighted edges are drawn last so they are on top of the others
+    let (highlighted, normal): (Vec<_>, Vec<_>) = graph
+        .edges()
+        .filter(|(from, to)| from != to)
+        .partition(|&(from, to)| style.is_highlighted(graph, from, to));
+    for (edges, color, width) in [
+        (normal, EDGE_COLOR, 1.0),
+        (highlighted, HIGHLIGHT_COLOR, 3.0),
+    ] {
+        for (from, to) in edges {
+            let (a, b) = (to_pixel(positions[from]), to_pixel(positions[to]));
+            if graph.is_directed() {
+                // End the arrow at the border of the target node
+                let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+                let length = (dx * dx + dy * dy).sqrt().max(f64::EPSILON);
+                let end = (b.0 - dx / length * radius, b.1 - dy / length * radius);
+                svg.arrow(a, end, color, width);
+            } else {
+                svg.line(a, b, color, width);
+            }
+        }
+    }
+
+    for (id, &p) in positions.iter().enumerate() {
+        let group = style.groups.get(id).copied().unwrap_or(0);
+        let center = to_pixel(p);
+        svg.circle(center, radius, GROUP_COLORS[group % GROUP_COLORS.len()]);
+        svg.title(graph.name(id));
+        if style.labels {
+            svg.text(
+                (center.0, center.1 - radius - 6.0),
+                graph.name(id),
+                10.0,
+                "black",
+            );
+        }
+    }
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(a: Point, b: Point) -> f64 {
+        ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+    }
+
+    #[test]
+    fn test_force_directed() {
+        // Two triangles connected by a single edge
+        let mut graph = Graph::undirected();
+        for (a, b) in [
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("x", "y"),
+            ("y", "z"),
+            ("z", "x"),
+            ("c", "x"),
+        ] {
+            graph.add_edge(a, b);
+        }
+        let positions = force_directed(&graph, 200, 1);
+        assert_eq!(positions, force_directed(&graph, 200, 1));
+        assert!(positions
+            .iter()
+            .all(|&(x, y)| (0.0..=1.0).contains(&x) && (0.0..=1.0).contains(&y)));
+
+        let id = |name: &str| graph.id(name).unwrap();
+        let within = distance(positions[id("a")], positions[id("b")]);
+        let between = distance(positions[id("a")], positions[id("y")]);
+        assert!(within < between, "{within} >= {between}");
+    }
+
+    #[test]
+    fn test_graph_to_svg() {
+        let mut graph = Graph::directed();
+        graph.add_edge("a", "b");
+        graph.add_edge("b", "c");
+        let style = GraphStyle {
+            groups: vec![0, 1, 1],
+            highlighted_edges: HashSet::from([(1, 2)]),
+            labels: true,
+        };
+        let svg = graph_to_svg(&graph, &force_directed(&graph, 50, 1), &style, 200.0).to_string();
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert_eq!(svg.matches("marker-end").count(), 2);
+        assert_eq!(svg.matches(HIGHLIGHT_COLOR).count(), 1);
+        assert_eq!(svg.matches(GROUP_COLORS[1]).count(), 2);
+        assert!(svg.contains("<title>c</title>"));
+    }
+}
@@ -0,0 +1,403 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Signed integer types that can be used in a `Rational`.
+pub trait Int:
+    Copy
+    + Ord
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+    + From<i8>
+{
+}
+
+impl<T> Int for T where
+    T: Copy
+        + Ord
+        + Display
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>
+        + Neg<Output = T>
+        + From<i8>
+{
+}
+
+pub fn gcd<T: Int>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::from(0) {
+        (a, b) = (b, a % b);
+    }
+    if a < T::from(0) {
+        -a
+    } else {
+        a
+    }
+}
+
+/// Exact fraction, always stored in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T> {
+    numerator: T,
+    denominator: T,
+}
+
+impl<T: Int> Rational<T> {
+    pub fn new(numerator: T, denominator: T) -> Self {
+        let zero = T::from(0);
+        if denominator == zero {
+            panic!("Rational with denominator 0");
+        }
+        let divisor = gcd(numerator, denominator);
+        let sign = if denominator < zero {
+            T::from(-1)
+        } else {
+            T::from(1)
+        };
+        Rational {
+            numerator: sign * numerator / divisor,
+            denominator: sign * denominator / divisor,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::from_int(T::from(0))
+    }
+
+    pub fn one() -> Self {
+        Self::from_int(T::from(1))
+    }
+
+    pub fn from_int(n: T) -> Self {
+        Rational {
+            numerator: n,
+            denominator: T::from(1),
+        }
+    }
+
+    pub fn numerator(&self) -> T {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> T {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == T::from(0)
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator == T::from(1)
+    }
+
+    pub fn to_integer(self) -> Option<T> {
+        self.is_integer().then_some(self.numerator)
+    }
+}
+
+impl<T: Int> From<T> for Rational<T> {
+    fn from(n: T) -> Self {
+        Self::from_int(n)
+    }
+}
+
+impl<T: Int> Add for Rational<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        // Divide by the gcd of the denominators first to keep intermediate values small
+        let divisor = gcd(self.denominator, other.denominator);
+        Rational::new(
+            self.numerator * (other.denominator / divisor)
+                + other.numerator * (self.denominator / divisor),
+            self.denominator / divisor * other.denominator,
+        )
+    }
+}
+
+impl<T: Int> Sub for Rational<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl<T: Int> Mul for Rational<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        // Cross-cancel first to keep intermediate values small
+        let a = gcd(self.numerator, other.denominator);
+        let b = gcd(other.numerator, self.denominator);
+        Rational::new(
+            (self.numerator / a) * (other.numerator / b),
+            (self.denominator / b) * (other.denominator / a),
+        )
+    }
+}
+
+impl<T: Int> Div for Rational<T> {
+    type Output = Self;
+
+    // Dividing is multiplying by the reciprocal
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self {
+        if other.is_zero() {
+            panic!("Division of rational by 0");
+        }
+        self * Rational::new(other.denominator, other.numerator)
+    }
+}
+
+impl<T: Int> Neg for Rational<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Rational {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl<T: Int> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Int> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Denominators are positive, so cross multiplication keeps the order
+        (self.numerator * other.denominator).cmp(&(other.numerator * self.denominator))
+    }
+}
+
+impl<T: Int> Display for Rational<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+pub type Q = Rational<i128>;
+
+/// System of linear equations `A * x = b` over exact rationals.
+#[derive(Debug, Clone)]
+pub struct LinearSystem {
+    // Augmented matrix [A | b], reduced to reduced row echelon form on construction
+    rows: Vec<Vec<Q>>,
+    nr_of_variables: usize,
+    pivots: Vec<usize>,
+    consistent: bool,
+}
+
+impl LinearSystem {
+    pub fn new(coefficients: &[Vec<i128>], constants: &[i128]) -> Self {
+        let nr_of_variables = coefficients.first().map_or(0, |row| row.len());
+        let mut rows: Vec<Vec<Q>> = coefficients
+            .iter()
+            .zip(constants)
+            .map(|(row, &b)| row.iter().chain([&b]).map(|&x| Q::from(x)).collect())
+            .collect();
+        let pivots = rref(&mut rows, nr_of_variables);
+        // A row 0 = b with b != 0 means there are no solutions
+        let consistent = rows
+            .iter()
+            .skip(pivots.len())
+            .all(|row| row[nr_of_variables].is_zero());
+
+        LinearSystem {
+            rows,
+            nr_of_variables,
+            pivots,
+            consistent,
+        }
+    }
+
+    /// The augmented matrix in reduced row echelon form.
+    pub fn rref(&self) -> &[Vec<Q>] {
+        &self.rows
+    }
+
+    pub fn rank(&self) -> usize {
+        self.pivots.len()
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.consistent
+    }
+
+    /// Columns that contain a pivot, the variables that are determined by the free variables.
+    pub fn pivot_variables(&self) -> &[usize] {
+        &self.pivots
+    }
+
+    pub fn free_variables(&self) -> Vec<usize> {
+        (0..self.nr_of_variables)
+            .filter(|v| !self.pivots.contains(v))
+            .collect()
+    }
+
+    /// Solve for all variables, given values for the free variables (in order of `free_variables`).
+    pub fn solve_with(&self, free_values: &[Q]) -> Option<Vec<Q>> {
+        if !self.consistent {
+            return None;
+        }
+        let mut x = vec![Q::zero(); self.nr_of_variables];
+        for (v, &value) in self.free_variables().iter().zip(free_values) {
+            x[*v] = value;
+        }
+        for (row, &pivot) in self.rows.iter().zip(&self.pivots) {
+            x[pivot] = (pivot + 1..self.nr_of_variables)
+                .fold(row[self.nr_of_variables], |acc, v| acc - row[v] * x[v]);
+        }
+        Some(x)
+    }
+
+    /// The solution if there is exactly one.
+    pub fn unique_solution(&self) -> Option<Vec<Q>> {
+        if self.rank() < self.nr_of_variables {
+            return None;
+        }
+        self.solve_with(&[])
+    }
+
+    /// Non-negative integer solution with `0 <= x[i] <= upper_bounds[i]` that minimises `objective . x`.
+    /// Enumerates every combination of values for the free variables, so only usable when there are few.
+    pub fn min_non_negative_integer_solution(
+        &self,
+        objective: &[i128],
+        upper_bounds: &[i128],
+    ) -> Option<(i128, Vec<i128>)> {
+        if !self.consistent {
+            return None;
+        }
+        let free = self.free_variables();
+        let mut values = vec![0; free.len()];
+        let mut best: Option<(i128, Vec<i128>)> = None;
+
+        loop {
+            let free_values: Vec<Q> = values.iter().map(|&v| Q::from(v)).collect();
+            let solution = self
+                .solve_with(&free_values)?
+                .iter()
+                .zip(upper_bounds)
+                .map(|(x, &max)| x.to_integer().filter(|&x| 0 <= x && x <= max))
+                .collect::<Option<Vec<i128>>>();
+            if let Some(solution) = solution {
+                let cost: i128 = solution.iter().zip(objective).map(|(x, c)| x * c).sum();
+                if best.as_ref().is_none_or(|(best_cost, _)| cost < *best_cost) {
+                    best = Some((cost, solution));
+                }
+            }
+
+            // Next combination of free values, counting like an odometer
+            let mut i = 0;
+            while i < free.len() && values[i] == upper_bounds[free[i]] {
+                values[i] = 0;
+                i += 1;
+            }
+            if i == free.len() {
+                return best;
+            }
+            values[i] += 1;
+        }
+    }
+}
+
+// Gaussian elimination of the first `columns` columns into reduced row echelon form. Returns pivot columns.
+fn rref(rows: &mut [Vec<Q>], columns: usize) -> Vec<usize> {
+    let mut pivots = Vec::new();
+    for column in 0..columns {
+        let r = pivots.len();
+        let Some(pivot_row) = (r..rows.len()).find(|&i| !rows[i][column].is_zero()) else {
+            continue;
+        };
+        rows.swap(r, pivot_row);
+
+        let pivot = rows[r][column];
+        rows[r].iter_mut().for_each(|x| *x = *x / pivot);
+        for i in (0..rows.len()).filter(|&i| i != r) {
+            let factor = rows[i][column];
+            if !factor.is_zero() {
+                for j in column..rows[i].len() {
+                    let delta = factor * rows[r][j];
+                    rows[i][j] = rows[i][j] - delta;
+                }
+            }
+        }
+
+        pivots.push(column);
+        if pivots.len() == rows.len() {
+            break;
+        }
+    }
+    pivots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rational() {
+        let a = Q::new(6, -8);
+        assert_eq!((a.numerator(), a.denominator()), (-3, 4));
+        assert_eq!(a + Q::new(1, 4), Q::new(-1, 2));
+        assert_eq!(a * Q::new(4, 3), Q::from(-1));
+        assert_eq!(a / Q::new(-3, 2), Q::new(1, 2));
+        assert!(Q::new(1, 3) < Q::new(1, 2));
+        assert_eq!(a.to_string(), "-3/4");
+    }
+
+    #[test]
+    fn test_unique_solution() {
+        // 94a + 22b = 8400, 34a + 67b = 5400
+        let system = LinearSystem::new(&[vec![94, 22], vec![34, 67]], &[8400, 5400]);
+        assert_eq!(
+            system.unique_solution(),
+            Some(vec![Q::from(80), Q::from(40)])
+        );
+
+        let inconsistent = LinearSystem::new(&[vec![1, 1], vec![2, 2]], &[1, 3]);
+        assert!(!inconsistent.is_consistent());
+    }
+
+    #[test]
+    fn test_free_variables_and_integer_solution() {
+        // Buttons (3) (1,3) (2) (2,3) (0,2) (0,1) with counters {3,5,4,7}
+        let coefficients = vec![
+            vec![0, 0, 0, 0, 1, 1],
+            vec![0, 1, 0, 0, 0, 1],
+            vec![0, 0, 1, 1, 1, 0],
+            vec![1, 1, 0, 1, 0, 0],
+        ];
+        let system = LinearSystem::new(&coefficients, &[3, 5, 4, 7]);
+        assert_eq!(system.rank(), 4);
+        assert_eq!(system.pivot_variables(), &[0, 1, 2, 4]);
+        assert_eq!(system.free_variables(), vec![3, 5]);
+        // x4 + x5 = 3
+        assert_eq!(
+            system.rref()[3],
+            [0, 0, 0, 0, 1, 1, 3].map(Q::from).to_vec()
+        );
+        let (presses, _) = system
+            .min_non_negative_integer_solution(&[1; 6], &[7, 5, 4, 4, 3, 3])
+            .unwrap();
+        assert_eq!(presses, 10);
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+/// Cache of results for recursive functions, keeping track of how often it was useful.
+/// The computation gets the memo passed in, so it can recurse with it (see `fibonacci` in the tests).
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// The cached value for the key, or the result of `compute` which is then cached.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Fraction of lookups that were answered from the cache.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> FromIterator<(K, V)> for Memo<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Memo {
+            cache: iter.into_iter().collect(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Display for Memo<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} entries, {} hits, {} misses ({:.1}% hit rate)",
+            self.len(),
+            self.hits,
+            self.misses,
+            100.0 * self.hit_rate()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        memo.get_or_compute(n, |memo| {
+            if n < 2 {
+                n
+            } else {
+                fibonacci(n - 1, memo) + fibonacci(n - 2, memo)
+            }
+        })
+    }
+
+    #[test]
+    fn test_recursion_and_stats() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(90, &mut memo), 2_880_067_194_370_816_120);
+        // Every n is computed once, fibonacci(n - 2) is a hit for n >= 3
+        assert_eq!(memo.len(), 91);
+        assert_eq!(memo.misses, 91);
+        assert_eq!(memo.hits, 88);
+
+        assert_eq!(fibonacci(90, &mut memo), 2_880_067_194_370_816_120);
+        assert_eq!(memo.hits, 89);
+        assert_eq!(
+            memo.to_string(),
+            "91 entries, 89 hits, 91 misses (49.4% hit rate)"
+        );
+    }
+}
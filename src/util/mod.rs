@@ -1,2 +1,28 @@
+// Only installed as global allocator with the `alloc-stats` feature
+#[cfg(any(test, feature = "alloc-stats"))]
+pub mod alloc;
+pub mod bits;
+pub mod compress;
+// Only tests compare implementations against each other
+#[cfg(test)]
+pub mod differential;
+pub mod dsu;
+pub mod frames;
+pub mod gif;
+pub mod graph;
+pub mod heatmap;
+pub mod layout;
+pub mod linalg;
+pub mod memo;
+pub mod parse;
+pub mod poly;
+pub mod polygon;
+pub mod ranges;
+pub mod render3d;
+pub mod report;
+pub mod scaling;
 pub mod solution;
+pub mod svg;
+pub mod terminal;
 pub mod util;
+pub mod visualize;
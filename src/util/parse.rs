@@ -0,0 +1,232 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+/// Every integer in the string, in order of appearance. A `-` directly in front of the digits makes
+/// the number negative, unless it follows another digit, e.g. the `-` of a range like `3-5`.
+pub fn ints<T>(s: &str) -> impl Iterator<Item = T> + '_
+where
+    T: FromStr,
+    T::Err: Debug,
+{
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let is_sign = bytes[i] == b'-'
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && (i == 0 || !bytes[i - 1].is_ascii_digit());
+            if is_sign || bytes[i].is_ascii_digit() {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                return Some(s[start..i].parse().expect("Could not parse integer"));
+            }
+            i += 1;
+        }
+        None
+    })
+}
+
+/// Every unsigned number in the bytes, scanned without allocating or validating UTF-8.
+/// Signs are ignored, so `-5` results in `5`.
+pub fn uints(bytes: &[u8]) -> impl Iterator<Item = u64> + '_ {
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return None;
+        }
+        let mut n = 0;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            n = n * 10 + (bytes[i] - b'0') as u64;
+            i += 1;
+        }
+        Some(n)
+    })
+}
+
+/// Sections of the input that are separated by blank lines. Handles `\r\n` line endings.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+    std::iter::from_fn(move || {
+        rest = rest.trim_start_matches(['\r', '\n']);
+        if rest.is_empty() {
+            return None;
+        }
+        let end = [rest.find("\n\n"), rest.find("\n\r\n")]
+            .into_iter()
+            .flatten()
+            .min()
+            .unwrap_or(rest.len());
+        let (section, remainder) = rest.split_at(end);
+        rest = remainder;
+        Some(section.trim_end_matches(['\r', '\n']))
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // 1-based line number in the input, 0 if parsing a single string
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+/// Fixed-format pattern where every `{}` captures a field, e.g. `"p={},{} v={},{}"`.
+pub struct Pattern<'p> {
+    // Literal text before, between and after the fields
+    literals: Vec<&'p str>,
+}
+
+impl<'p> Pattern<'p> {
+    pub fn new(pattern: &'p str) -> Self {
+        let literals: Vec<&str> = pattern.split("{}").collect();
+        if literals.len() < 2 {
+            panic!("Pattern {pattern:?} has no fields");
+        }
+        if literals[1..literals.len() - 1].iter().any(|l| l.is_empty()) {
+            panic!("Pattern {pattern:?} has adjacent fields that cannot be separated");
+        }
+        Pattern { literals }
+    }
+
+    /// Text of every field, where a field ends at the first occurrence of the literal after it.
+    pub fn fields<'s>(&self, s: &'s str) -> Result<Vec<&'s str>, String> {
+        let mut rest = s
+            .strip_prefix(self.literals[0])
+            .ok_or_else(|| format!("{s:?} does not start with {:?}", self.literals[0]))?;
+        let mut fields = Vec::with_capacity(self.literals.len() - 1);
+
+        for (i, literal) in self.literals.iter().enumerate().skip(1) {
+            let end = if i == self.literals.len() - 1 {
+                rest.strip_suffix(literal)
+                    .map(|field| field.len())
+                    .ok_or_else(|| format!("{s:?} does not end with {literal:?}"))?
+            } else {
+                rest.find(literal)
+                    .ok_or_else(|| format!("{s:?} is missing {literal:?}"))?
+            };
+            fields.push(&rest[..end]);
+            rest = &rest[end + literal.len()..];
+        }
+
+        Ok(fields)
+    }
+
+    pub fn parse<T: FromFields>(&self, s: &str) -> Result<T, ParseError> {
+        let error = |message| ParseError { line: 0, message };
+        T::from_fields(&self.fields(s).map_err(error)?).map_err(error)
+    }
+
+    /// Parse every line of the input, reporting the line number of the first line that fails.
+    pub fn parse_lines<T: FromFields>(&self, input: &str) -> Result<Vec<T>, ParseError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                self.parse(line).map_err(|e| ParseError {
+                    line: i + 1,
+                    message: e.message,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Types that can be built from the text of the fields captured by a `Pattern`.
+pub trait FromFields: Sized {
+    fn from_fields(fields: &[&str]) -> Result<Self, String>;
+}
+
+fn parse_field<T>(field: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    field
+        .parse()
+        .map_err(|e| format!("Could not parse {field:?}: {e}"))
+}
+
+macro_rules! impl_from_fields {
+    ($n:literal; $($type_:ident $index:tt),+) => {
+        impl<$($type_),+> FromFields for ($($type_,)+)
+        where
+            $($type_: FromStr, $type_::Err: Display),+
+        {
+            fn from_fields(fields: &[&str]) -> Result<Self, String> {
+                if fields.len() != $n {
+                    return Err(format!("Expected {} fields but got {}", $n, fields.len()));
+                }
+                Ok(($(parse_field::<$type_>(fields[$index])?,)+))
+            }
+        }
+    };
+}
+
+impl_from_fields!(1; A 0);
+impl_from_fields!(2; A 0, B 1);
+impl_from_fields!(3; A 0, B 1, C 2);
+impl_from_fields!(4; A 0, B 1, C 2, D 3);
+impl_from_fields!(5; A 0, B 1, C 2, D 3, E 4);
+impl_from_fields!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ints() {
+        let nums: Vec<i64> = ints("p=0,-4 v=3,-3 range 3-5 x-7").collect();
+        assert_eq!(nums, vec![0, -4, 3, -3, 3, 5, -7]);
+        let nums: Vec<u64> = uints(b"Register A: 729, -12").collect();
+        assert_eq!(nums, vec![729, 12]);
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "a\nb\n\n\nc\r\n\r\nd\n";
+        assert_eq!(sections(input).collect::<Vec<_>>(), vec!["a\nb", "c", "d"]);
+    }
+
+    #[test]
+    fn test_pattern() {
+        let pattern = Pattern::new("p={},{} v={},{}");
+        assert_eq!(
+            pattern.parse::<(i32, i32, i32, i32)>("p=0,4 v=3,-3"),
+            Ok((0, 4, 3, -3))
+        );
+        assert_eq!(
+            Pattern::new("{}: {}").parse::<(String, u8)>("name: 12"),
+            Ok(("name".to_string(), 12))
+        );
+
+        let error = pattern
+            .parse_lines::<(i32, i32, i32, i32)>("p=0,4 v=3,-3\np=1,x v=2,2")
+            .unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(
+            error.to_string(),
+            "line 2: Could not parse \"x\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Pattern \"p=0\" has no fields")]
+    fn test_pattern_without_fields() {
+        Pattern::new("p=0");
+    }
+}
@@ -0,0 +1,146 @@
+use crate::util::linalg::Q;
+
+/// Polynomial with exact rational coefficients, lowest degree first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<Q>,
+}
+
+impl Polynomial {
+    pub fn new(coefficients: Vec<Q>) -> Self {
+        let mut polynomial = Polynomial { coefficients };
+        polynomial.trim();
+        polynomial
+    }
+
+    // Leading zero coefficients do not contribute to the degree
+    fn trim(&mut self) {
+        while self.coefficients.last().is_some_and(|c| c.is_zero()) {
+            self.coefficients.pop();
+        }
+    }
+
+    pub fn coefficients(&self) -> &[Q] {
+        &self.coefficients
+    }
+
+    /// Degree of the polynomial, None for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// Evaluate with Horner's method.
+    pub fn eval(&self, x: Q) -> Q {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Q::zero(), |acc, &c| acc * x + c)
+    }
+
+    /// Evaluate at a whole number, for polynomials that are known to produce whole numbers there.
+    pub fn eval_integer(&self, x: i128) -> i128 {
+        self.eval(Q::from(x))
+            .to_integer()
+            .expect("Polynomial does not evaluate to a whole number")
+    }
+}
+
+/// Lagrange interpolation: the polynomial of lowest degree that passes through all points.
+/// https://en.wikipedia.org/wiki/Lagrange_polynomial
+pub fn lagrange(points: &[(i128, i128)]) -> Polynomial {
+    let mut coefficients = vec![Q::zero(); points.len()];
+
+    for (i, &(x_i, y_i)) in points.iter().enumerate() {
+        // Basis polynomial that is 1 at x_i and 0 at every other x_j
+        let mut basis = vec![Q::one()];
+        let mut denominator = Q::one();
+        for (j, &(x_j, _)) in points.iter().enumerate().filter(|(j, _)| *j != i) {
+            if x_i == x_j {
+                panic!("Cannot interpolate points with the same x = {x_i} (indices {i} and {j})");
+            }
+            // Multiply basis by (x - x_j)
+            let mut next = vec![Q::zero(); basis.len() + 1];
+            for (k, &c) in basis.iter().enumerate() {
+                next[k + 1] = next[k + 1] + c;
+                next[k] = next[k] - c * Q::from(x_j);
+            }
+            basis = next;
+            denominator = denominator * Q::from(x_i - x_j);
+        }
+
+        let scale = Q::from(y_i) / denominator;
+        for (k, c) in basis.into_iter().enumerate() {
+            coefficients[k] = coefficients[k] + c * scale;
+        }
+    }
+
+    Polynomial::new(coefficients)
+}
+
+/// Leading entries of the forward difference table of `sequence`: f(0), Δf(0), Δ²f(0), ...
+/// Stops as soon as a row of differences is all zeros.
+pub fn forward_differences(sequence: &[i128]) -> Vec<i128> {
+    let mut leading = Vec::new();
+    let mut row = sequence.to_vec();
+    while row.iter().any(|&x| x != 0) {
+        leading.push(row[0]);
+        row = row.windows(2).map(|w| w[1] - w[0]).collect();
+    }
+    leading
+}
+
+/// Degree of the polynomial that generates the sequence, if the sequence is long enough to tell.
+/// The zero sequence has no degree, and neither does a sequence without a constant row of differences.
+pub fn degree(sequence: &[i128]) -> Option<usize> {
+    let differences = forward_differences(sequence);
+    // The last non-zero row must be constant and contain at least 2 values to be confirmed by a zero row
+    (!differences.is_empty() && differences.len() < sequence.len()).then(|| differences.len() - 1)
+}
+
+/// Newton forward-difference interpolation: value at index `n` of the polynomial with `sequence[i] = f(i)`.
+/// Works for any integer `n`, also negative or huge ones, without leaving the integers.
+pub fn extrapolate(sequence: &[i128], n: i128) -> i128 {
+    // f(n) = Σ Δᵏf(0) * C(n, k), where C(n, k) = C(n, k - 1) * (n - k + 1) / k is always whole
+    let mut binomial = 1;
+    let mut result = 0;
+    for (k, difference) in forward_differences(sequence).into_iter().enumerate() {
+        if k > 0 {
+            binomial = binomial * (n - k as i128 + 1) / k as i128;
+        }
+        result += difference * binomial;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lagrange() {
+        let polynomial = lagrange(&[(0, 1), (1, 3), (2, 7), (3, 13)]);
+        // x² + x + 1
+        assert_eq!(
+            polynomial.coefficients(),
+            &[Q::from(1), Q::from(1), Q::from(1)]
+        );
+        assert_eq!(polynomial.degree(), Some(2));
+        assert_eq!(
+            polynomial.eval_integer(1_000_000_000),
+            1_000_000_001_000_000_001
+        );
+        assert_eq!(polynomial.eval(Q::new(1, 2)), Q::new(7, 4));
+    }
+
+    #[test]
+    fn test_forward_differences() {
+        let sequence = [1, 3, 6, 10, 15, 21];
+        assert_eq!(forward_differences(&sequence), vec![1, 2, 1]);
+        assert_eq!(degree(&sequence), Some(2));
+        assert_eq!(degree(&[1, 2, 4]), None);
+        assert_eq!(degree(&[0, 0, 0]), None);
+        assert_eq!(extrapolate(&sequence, 6), 28);
+        assert_eq!(extrapolate(&sequence, -1), 0);
+        assert_eq!(extrapolate(&sequence, 1_000_000), 500_001_500_001);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::util::linalg::gcd;
+
+/// Lattice point (x, y). Polygons are given as their vertices in order, the last one connects back to the first.
+pub type Point = (i64, i64);
+
+fn edges(vertices: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+/// Twice the area of the polygon with the shoelace formula - https://en.wikipedia.org/wiki/Shoelace_formula
+/// Doubled to stay exact, since the area of a lattice polygon can be a multiple of 1/2.
+pub fn double_area(vertices: &[Point]) -> i64 {
+    signed_double_area(vertices).abs()
+}
+
+/// Like `double_area`, but positive when the vertices go counterclockwise (with the y axis pointing up).
+pub fn signed_double_area(vertices: &[Point]) -> i64 {
+    edges(vertices).map(|(a, b)| a.0 * b.1 - b.0 * a.1).sum()
+}
+
+/// Nr of lattice points on the edges of the polygon.
+pub fn boundary_points(vertices: &[Point]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| gcd(b.0 - a.0, b.1 - a.1))
+        .sum()
+}
+
+/// Nr of lattice points strictly inside the polygon with Pick's theorem - https://en.wikipedia.org/wiki/Pick%27s_theorem
+/// A = I + B/2 - 1  =>  I = (2A - B + 2) / 2
+pub fn interior_points(vertices: &[Point]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+/// Nr of lattice points inside or on the edges of the polygon.
+/// For a loop dug through the centers of unit squares, this is the nr of squares it encloses.
+pub fn enclosed_points(vertices: &[Point]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+pub fn on_boundary(vertices: &[Point], p: Point) -> bool {
+    edges(vertices).any(|(a, b)| {
+        let cross = (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0);
+        cross == 0
+            && a.0.min(b.0) <= p.0
+            && p.0 <= a.0.max(b.0)
+            && a.1.min(b.1) <= p.1
+            && p.1 <= a.1.max(b.1)
+    })
+}
+
+/// Even-odd rule: a ray from the point crosses the edges an odd nr of times. Points on the boundary are inside.
+pub fn contains_even_odd(vertices: &[Point], p: Point) -> bool {
+    if on_boundary(vertices, p) {
+        return true;
+    }
+    let mut inside = false;
+    for (a, b) in edges(vertices) {
+        // Edge crosses the horizontal line through p, check whether that happens to the right of p
+        if (a.1 > p.1) != (b.1 > p.1) {
+            let lhs = (p.0 - a.0) * (b.1 - a.1);
+            let rhs = (b.0 - a.0) * (p.1 - a.1);
+            if (b.1 > a.1 && lhs < rhs) || (b.1 < a.1 && lhs > rhs) {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Nr of times the polygon winds counterclockwise around the point - https://en.wikipedia.org/wiki/Winding_number
+pub fn winding_number(vertices: &[Point], p: Point) -> i64 {
+    let is_left = |a: Point, b: Point| (b.0 - a.0) * (p.1 - a.1) - (p.0 - a.0) * (b.1 - a.1);
+    edges(vertices)
+        .map(|(a, b)| {
+            if a.1 <= p.1 && b.1 > p.1 && is_left(a, b) > 0 {
+                1
+            } else if a.1 > p.1 && b.1 <= p.1 && is_left(a, b) < 0 {
+                -1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+/// Non-zero rule: the polygon winds around the point. Points on the boundary are inside.
+pub fn contains_nonzero(vertices: &[Point], p: Point) -> bool {
+    on_boundary(vertices, p) || winding_number(vertices, p) != 0
+}
+
+/// Whether the axis-aligned rectangle with the given opposite corners lies fully inside the rectilinear
+/// polygon, where points on the boundary of the polygon count as inside.
+pub fn rectangle_inside_rectilinear(vertices: &[Point], corner_1: Point, corner_2: Point) -> bool {
+    let (x_min, x_max) = (corner_1.0.min(corner_2.0), corner_1.0.max(corner_2.0));
+    let (y_min, y_max) = (corner_1.1.min(corner_2.1), corner_1.1.max(corner_2.1));
+
+    // An edge through the open inside of the rectangle has the outside of the polygon on one of its sides
+    let cuts = |at: i64, from: i64, to: i64, min: i64, max: i64, other_min: i64, other_max: i64| {
+        min < at && at < max && from.min(to) < other_max && from.max(to) > other_min
+    };
+    let cut = edges(vertices).any(|(a, b)| {
+        if a.0 == b.0 {
+            cuts(a.0, a.1, b.1, x_min, x_max, y_min, y_max)
+        } else {
+            cuts(a.1, a.0, b.0, y_min, y_max, x_min, x_max)
+        }
+    });
+    if cut {
+        return false;
+    }
+
+    // Work with doubled coordinates to keep centers on the lattice
+    let doubled: Vec<Point> = vertices.iter().map(|p| (2 * p.0, 2 * p.1)).collect();
+    if x_min < x_max && y_min < y_max {
+        // Nothing cuts through, so the rectangle is fully inside or fully outside -> check its center
+        return contains_even_odd(&doubled, (x_min + x_max, y_min + y_max));
+    }
+
+    // A line can run along an edge and leave the polygon past a corner without cutting through an edge,
+    // so check every piece of it between the coordinates of the vertices
+    let horizontal = x_min < x_max;
+    let (min, max) = if horizontal {
+        (x_min, x_max)
+    } else {
+        (y_min, y_max)
+    };
+    let mut breakpoints: Vec<i64> = vertices
+        .iter()
+        .map(|p| if horizontal { p.0 } else { p.1 })
+        .filter(|&c| min < c && c < max)
+        .chain([min, max])
+        .collect();
+    breakpoints.sort_unstable();
+    breakpoints.dedup();
+    let to_point = |c: i64| {
+        if horizontal {
+            (c, 2 * y_min)
+        } else {
+            (2 * x_min, c)
+        }
+    };
+    contains_even_odd(&doubled, to_point(2 * min))
+        && breakpoints.windows(2).all(|w| {
+            contains_even_odd(&doubled, to_point(w[0] + w[1]))
+                && contains_even_odd(&doubled, to_point(2 * w[1]))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Dig plan of 2023 day 18 example, shaped like
+    // #######
+    // #.....#
+    // ###...#
+    // ..#...#
+    // ..#...#
+    // ###.###
+    // #...#..
+    // ##..###
+    // .#....#
+    // .######
+    const LAGOON: [Point; 14] = [
+        (0, 0),
+        (6, 0),
+        (6, 5),
+        (4, 5),
+        (4, 7),
+        (6, 7),
+        (6, 9),
+        (1, 9),
+        (1, 7),
+        (0, 7),
+        (0, 5),
+        (2, 5),
+        (2, 2),
+        (0, 2),
+    ];
+
+    #[test]
+    fn test_area_and_pick() {
+        assert_eq!(double_area(&LAGOON), 2 * 42);
+        assert_eq!(boundary_points(&LAGOON), 38);
+        assert_eq!(interior_points(&LAGOON), 24);
+        assert_eq!(enclosed_points(&LAGOON), 62);
+        assert_eq!(double_area(&[(0, 0), (1, 0), (0, 1)]), 1);
+        assert_eq!(signed_double_area(&[(0, 0), (0, 1), (1, 0)]), -1);
+    }
+
+    #[test]
+    fn test_point_in_polygon() {
+        for (point, inside) in [
+            ((3, 3), true),
+            ((1, 3), false),
+            ((0, 1), true),
+            ((2, 4), true),
+            ((5, 6), false),
+            ((7, 1), false),
+        ] {
+            assert_eq!(contains_even_odd(&LAGOON, point), inside, "{point:?}");
+            assert_eq!(contains_nonzero(&LAGOON, point), inside, "{point:?}");
+        }
+        // Counterclockwise square winds once, clockwise square winds -1
+        let square = [(0, 0), (4, 0), (4, 4), (0, 4)];
+        assert_eq!(winding_number(&square, (2, 2)), 1);
+        let reversed: Vec<Point> = square.iter().rev().copied().collect();
+        assert_eq!(winding_number(&reversed, (2, 2)), -1);
+    }
+
+    #[test]
+    fn test_rectangle_inside_rectilinear() {
+        assert!(rectangle_inside_rectilinear(&LAGOON, (2, 2), (6, 5)));
+        assert!(rectangle_inside_rectilinear(&LAGOON, (0, 0), (6, 2)));
+        assert!(rectangle_inside_rectilinear(&LAGOON, (1, 7), (6, 9)));
+        assert!(!rectangle_inside_rectilinear(&LAGOON, (0, 0), (6, 5)));
+        assert!(!rectangle_inside_rectilinear(&LAGOON, (2, 5), (6, 7)));
+        // Line along the notch at x = 4 between y = 5 and y = 7 is on the boundary
+        assert!(rectangle_inside_rectilinear(&LAGOON, (4, 5), (4, 7)));
+        assert!(!rectangle_inside_rectilinear(&LAGOON, (5, 4), (5, 8)));
+        // Line along the top edge that continues past the corner
+        assert!(rectangle_inside_rectilinear(&LAGOON, (0, 0), (6, 0)));
+        assert!(!rectangle_inside_rectilinear(&LAGOON, (0, 0), (8, 0)));
+    }
+}
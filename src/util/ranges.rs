@@ -0,0 +1,296 @@
+use std::cmp::{max, min};
+use std::ops::{Add, Range, Sub};
+
+/// Values that can be used as bounds of ranges in a `RangeSet` or `RangeMap`.
+pub trait Bound: Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Default> Bound for T {}
+
+/// Split a half-open range into the part below `at` and the part at or above `at`.
+pub fn split_at<T: Bound>(range: &Range<T>, at: T) -> (Option<Range<T>>, Option<Range<T>>) {
+    let below = range.start..min(range.end, at);
+    let above = max(range.start, at)..range.end;
+    (
+        (!below.is_empty()).then_some(below),
+        (!above.is_empty()).then_some(above),
+    )
+}
+
+pub fn intersect<T: Bound>(a: &Range<T>, b: &Range<T>) -> Option<Range<T>> {
+    let range = max(a.start, b.start)..min(a.end, b.end);
+    (!range.is_empty()).then_some(range)
+}
+
+/// A set of values stored as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Bound> RangeSet<T> {
+    pub fn new() -> Self {
+        RangeSet { ranges: Vec::new() }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    /// Total number of values contained in the set.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::default(), |total, r| total + (r.end - r.start))
+    }
+
+    pub fn contains(&self, x: &T) -> bool {
+        // First range whose end lies past x is the only candidate that can contain it
+        let i = self.ranges.partition_point(|r| r.end <= *x);
+        i < self.ranges.len() && self.ranges[i].start <= *x
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        // Ranges that overlap or touch the new range get merged into it
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if first < last {
+            min(range.start, self.ranges[first].start)..max(range.end, self.ranges[last - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        other.ranges.iter().for_each(|r| result.insert(r.clone()));
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            if let Some(r) = intersect(a, b) {
+                ranges.push(r);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        for range in &self.ranges {
+            let mut rest = Some(range.clone());
+            for cut in &other.ranges {
+                let Some(r) = rest.take() else { break };
+                if cut.end <= r.start {
+                    rest = Some(r);
+                    continue;
+                }
+                let (below, above) = split_at(&r, cut.start);
+                ranges.extend(below);
+                rest = above.and_then(|above| split_at(&above, cut.end).1);
+            }
+            ranges.extend(rest);
+        }
+        RangeSet { ranges }
+    }
+}
+
+impl<T: Bound> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ranges: Vec<Range<T>> = iter.into_iter().filter(|r| !r.is_empty()).collect();
+        ranges.sort_by_key(|r| r.start);
+
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end => last.end = max(last.end, r.end),
+                _ => merged.push(r),
+            }
+        }
+        RangeSet { ranges: merged }
+    }
+}
+
+/// Piecewise mapping of source ranges onto destination ranges of the same length.
+/// Values outside of every source range map onto themselves.
+#[derive(Debug, Clone, Default)]
+pub struct RangeMap<T> {
+    // Sorted, non-overlapping source ranges and the value their start maps to
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T: Bound> RangeMap<T> {
+    pub fn new() -> Self {
+        RangeMap { pieces: Vec::new() }
+    }
+
+    /// Map `source.start + i` to `destination + i` for every value in `source`.
+    /// Panics if `source` overlaps a previously added source range.
+    pub fn add(&mut self, source: Range<T>, destination: T) {
+        if source.is_empty() {
+            return;
+        }
+        let i = self.pieces.partition_point(|(s, _)| s.start < source.start);
+        let overlaps_prev = i > 0 && self.pieces[i - 1].0.end > source.start;
+        let overlaps_next = i < self.pieces.len() && self.pieces[i].0.start < source.end;
+        if overlaps_prev || overlaps_next {
+            panic!("Source ranges of a RangeMap must not overlap");
+        }
+        self.pieces.insert(i, (source, destination));
+    }
+
+    pub fn map(&self, x: T) -> T {
+        let i = self.pieces.partition_point(|(s, _)| s.end <= x);
+        match self.pieces.get(i) {
+            Some((source, destination)) if source.start <= x => *destination + (x - source.start),
+            _ => x,
+        }
+    }
+
+    /// Split the given range into sub-ranges, each paired with the value its start maps to.
+    fn map_pieces(&self, range: &Range<T>) -> Vec<(Range<T>, T)> {
+        let mut result = Vec::new();
+        let mut position = range.start;
+        for (source, destination) in &self.pieces {
+            if source.end <= position {
+                continue;
+            }
+            if source.start >= range.end {
+                break;
+            }
+            if position < source.start {
+                result.push((position..source.start, position));
+            }
+            let overlap = max(position, source.start)..min(range.end, source.end);
+            let start = *destination + (overlap.start - source.start);
+            position = overlap.end;
+            result.push((overlap, start));
+        }
+        if position < range.end {
+            result.push((position..range.end, position));
+        }
+        result
+    }
+
+    pub fn map_range(&self, range: &Range<T>) -> RangeSet<T> {
+        self.map_pieces(range)
+            .into_iter()
+            .map(|(r, start)| start..start + (r.end - r.start))
+            .collect()
+    }
+
+    pub fn map_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        set.ranges()
+            .iter()
+            .flat_map(|r| self.map_range(r).ranges)
+            .collect()
+    }
+
+    /// Create the map equivalent to applying `self` first and `next` second.
+    pub fn compose(&self, next: &Self) -> Self {
+        let mut result = RangeMap::new();
+
+        // Values covered by one of our pieces go through both maps
+        for (source, destination) in &self.pieces {
+            let image = *destination..*destination + (source.end - source.start);
+            for (r, start) in next.map_pieces(&image) {
+                result.add(
+                    source.start + (r.start - *destination)..source.start + (r.end - *destination),
+                    start,
+                );
+            }
+        }
+
+        // Values outside of our pieces map onto themselves, so only `next` applies to them
+        let ours: RangeSet<T> = self.pieces.iter().map(|(s, _)| s.clone()).collect();
+        for (source, destination) in &next.pieces {
+            let uncovered = RangeSet::from_iter([source.clone()]).difference(&ours);
+            for r in uncovered.ranges() {
+                result.add(r.clone(), *destination + (r.start - source.start));
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_set_normalizes() {
+        let set: RangeSet<i32> = [5..8, 1..3, 2..4, 8..10, 12..12].into_iter().collect();
+        assert_eq!(set.ranges(), &[1..4, 5..10]);
+        assert_eq!(set.len(), 8);
+        assert!(set.contains(&9));
+        assert!(!set.contains(&4));
+
+        let mut inserted = RangeSet::new();
+        [5..8, 1..3, 2..4, 8..10]
+            .into_iter()
+            .for_each(|r| inserted.insert(r));
+        assert_eq!(inserted, set);
+    }
+
+    fn bounds(set: &RangeSet<i32>) -> Vec<(i32, i32)> {
+        set.ranges().iter().map(|r| (r.start, r.end)).collect()
+    }
+
+    #[test]
+    fn test_range_set_algebra() {
+        let a: RangeSet<i32> = [0..10, 20..30].into_iter().collect();
+        let b: RangeSet<i32> = std::iter::once(5..25).collect();
+        assert_eq!(bounds(&a.union(&b)), vec![(0, 30)]);
+        assert_eq!(bounds(&a.intersection(&b)), vec![(5, 10), (20, 25)]);
+        // Touching ranges merge, ranges that only touch have nothing in common
+        let c: RangeSet<i32> = std::iter::once(10..20).collect();
+        assert_eq!(bounds(&a.union(&c)), vec![(0, 30)]);
+        assert_eq!(bounds(&a.intersection(&c)), vec![]);
+        assert_eq!(bounds(&a.difference(&b)), vec![(0, 5), (25, 30)]);
+        assert_eq!(bounds(&b.difference(&a)), vec![(10, 20)]);
+    }
+
+    #[test]
+    fn test_split_at() {
+        assert_eq!(split_at(&(1..10), 5), (Some(1..5), Some(5..10)));
+        assert_eq!(split_at(&(1..10), 0), (None, Some(1..10)));
+        assert_eq!(split_at(&(1..10), 10), (Some(1..10), None));
+    }
+
+    #[test]
+    fn test_range_map_compose() {
+        let mut first = RangeMap::new();
+        first.add(98..100, 50);
+        first.add(50..98, 52);
+        let mut second = RangeMap::new();
+        second.add(15..52, 0);
+        second.add(52..54, 37);
+        second.add(0..15, 39);
+
+        let composed = first.compose(&second);
+        for x in 0..120 {
+            assert_eq!(composed.map(x), second.map(first.map(x)), "x = {x}");
+        }
+        assert_eq!(
+            composed.map_range(&(40..110)),
+            second.map_set(&first.map_range(&(40..110)))
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use image::{Rgb, RgbImage};
+
+/// Point (x, y, z) with z pointing up.
+pub type Vec3 = [f64; 3];
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: Vec3, b: Vec3) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(a: Vec3) -> Vec3 {
+    let length = dot(a, a).sqrt();
+    [a[0] / length, a[1] / length, a[2] / length]
+}
+
+/// Orthographic camera looking at the scene from far away.
+/// The azimuth turns around the z axis starting from the x axis, the elevation is the angle above the xy plane.
+#[derive(Debug, Clone, Copy)]
+pub struct View {
+    pub azimuth_degrees: f64,
+    pub elevation_degrees: f64,
+    pub pixels_per_unit: f64,
+}
+
+impl Default for View {
+    fn default() -> Self {
+        View {
+            azimuth_degrees: 35.0,
+            elevation_degrees: 25.0,
+            pixels_per_unit: 10.0,
+        }
+    }
+}
+
+impl View {
+    // Unit vector pointing from the scene to the camera
+    fn camera_direction(&self) -> Vec3 {
+        let (azimuth, elevation) = (
+            self.azimuth_degrees.to_radians(),
+            self.elevation_degrees.to_radians(),
+        );
+        [
+            elevation.cos() * azimuth.cos(),
+            elevation.cos() * azimuth.sin(),
+            elevation.sin(),
+        ]
+    }
+
+    /// Screen x, screen y (up) and depth (bigger is closer to the camera) of a point.
+    fn project(&self, p: Vec3) -> Vec3 {
+        let to_camera = self.camera_direction();
+        // Horizontal and square to the camera direction. Taken from the azimuth rather than from
+        // cross(z, to_camera), which is 0 when looking straight down or up.
+        let azimuth = self.azimuth_degrees.to_radians();
+        let right = [-azimuth.sin(), azimuth.cos(), 0.0];
+        let up = cross(to_camera, right);
+        [dot(p, right), dot(p, up), dot(p, to_camera)]
+    }
+}
+
+/// Scene of solid boxes and line segments, rendered in software so it works without a GPU or window.
+#[derive(Debug, Clone)]
+pub struct Scene {
+    boxes: Vec<(Vec3, Vec3, Rgb<u8>)>,
+    segments: Vec<(Vec3, Vec3, Rgb<u8>)>,
+    background: Rgb<u8>,
+}
+
+impl Scene {
+    pub fn new(background: Rgb<u8>) -> Self {
+        Scene {
+            boxes: Vec::new(),
+            segments: Vec::new(),
+            background,
+        }
+    }
+
+    /// Axis aligned box between two opposite corners.
+    pub fn add_box(&mut self, min: Vec3, max: Vec3, color: Rgb<u8>) {
+        self.boxes.push((min, max, color));
+    }
+
+    pub fn add_segment(&mut self, from: Vec3, to: Vec3, color: Rgb<u8>) {
+        self.segments.push((from, to, color));
+    }
+
+    fn triangles(&self) -> Vec<([Vec3; 3], Vec3, Rgb<u8>)> {
+        let mut triangles = Vec::with_capacity(self.boxes.len() * 12);
+        for &(min, max, color) in &self.boxes {
+            let corner = |i: usize| -> Vec3 {
+                [
+                    if i & 1 == 0 { min[0] } else { max[0] },
+                    if i & 2 == 0 { min[1] } else { max[1] },
+                    if i & 4 == 0 { min[2] } else { max[2] },
+                ]
+            };
+            // Faces as corner indices, counterclockwise seen from outside, with their normal
+            let faces: [([usize; 4], Vec3); 6] = [
+                ([0, 2, 6, 4], [-1.0, 0.0, 0.0]),
+                ([1, 5, 7, 3], [1.0, 0.0, 0.0]),
+                ([0, 4, 5, 1], [0.0, -1.0, 0.0]),
+                ([2, 3, 7, 6], [0.0, 1.0, 0.0]),
+                ([0, 1, 3, 2], [0.0, 0.0, -1.0]),
+                ([4, 6, 7, 5], [0.0, 0.0, 1.0]),
+            ];
+            for (face, normal) in faces {
+                let [a, b, c, d] = face.map(corner);
+                triangles.push(([a, b, c], normal, color));
+                triangles.push(([a, c, d], normal, color));
+            }
+        }
+        triangles
+    }
+
+    pub fn render(&self, view: &View) -> RgbImage {
+        let to_camera = view.camera_direction();
+        let triangles: Vec<([Vec3; 3], Vec3, Rgb<u8>)> = self
+            .triangles()
+            .into_iter()
+            .filter(|(_, normal, _)| dot(*normal, to_camera) > 0.0)
+            .collect();
+        let points: Vec<Vec3> = triangles
+            .iter()
+            .flat_map(|(corners, _, _)| corners.iter().copied())
+            .chain(self.segments.iter().flat_map(|&(a, b, _)| [a, b]))
+            .map(|p| view.project(p))
+            .collect();
+        if points.is_empty() {
+            return RgbImage::from_pixel(1, 1, self.background);
+        }
+
+        // Fit the scene in the image with a margin around it
+        let margin = 10.0;
+        let bound = |axis: usize, f: fn(f64, f64) -> f64, init: f64| {
+            points.iter().map(|p| p[axis]).fold(init, f)
+        };
+        let (x_min, x_max) = (bound(0, f64::min, f64::MAX), bound(0, f64::max, f64::MIN));
+        let (y_min, y_max) = (bound(1, f64::min, f64::MAX), bound(1, f64::max, f64::MIN));
+        let scale = view.pixels_per_unit;
+        let width = ((x_max - x_min) * scale + 2.0 * margin).ceil() as u32;
+        let height = ((y_max - y_min) * scale + 2.0 * margin).ceil() as u32;
+        let to_pixel = |p: Vec3| -> Vec3 {
+            let [x, y, depth] = view.project(p);
+            [
+                (x - x_min) * scale + margin,
+                (y_max - y) * scale + margin,
+                depth,
+            ]
+        };
+
+        let mut img = RgbImage::from_pixel(width, height, self.background);
+        let mut depths = vec![f64::NEG_INFINITY; (width * height) as usize];
+        let mut plot = |x: i64, y: i64, depth: f64, color: Rgb<u8>| {
+            if x >= 0 && y >= 0 && x < width as i64 && y < height as i64 {
+                let i = (y * width as i64 + x) as usize;
+                if depth > depths[i] {
+                    depths[i] = depth;
+                    img.put_pixel(x as u32, y as u32, color);
+                }
+            }
+        };
+
+        // Light comes from above the camera, faces turned away from it are darker
+        let light = normalize([to_camera[0], to_camera[1], to_camera[2] + 1.0]);
+        for (corners, normal, color) in &triangles {
+            let brightness = 0.4 + 0.6 * dot(*normal, light).max(0.0);
+            let shaded = Rgb(color.0.map(|c| (c as f64 * brightness).round() as u8));
+            let [a, b, c] = corners.map(to_pixel);
+            let area = (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+            if area.abs() < f64::EPSILON {
+                continue;
+            }
+            let x_range =
+                a[0].min(b[0]).min(c[0]).floor() as i64..=a[0].max(b[0]).max(c[0]).ceil() as i64;
+            let y_range =
+                a[1].min(b[1]).min(c[1]).floor() as i64..=a[1].max(b[1]).max(c[1]).ceil() as i64;
+            for y in y_range {
+                for x in x_range.clone() {
+                    // Barycentric coordinates of the pixel center
+                    let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+                    let w_a = ((b[0] - px) * (c[1] - py) - (b[1] - py) * (c[0] - px)) / area;
+                    let w_b = ((c[0] - px) * (a[1] - py) - (c[1] - py) * (a[0] - px)) / area;
+                    let w_c = 1.0 - w_a - w_b;
+                    if w_a >= 0.0 && w_b >= 0.0 && w_c >= 0.0 {
+                        plot(x, y, w_a * a[2] + w_b * b[2] + w_c * c[2], shaded);
+                    }
+                }
+            }
+        }
+
+        // Segments are drawn as dots every half pixel, a bit thicker when zoomed in
+        let radius = (scale / 10.0).clamp(0.0, 3.0) as i64;
+        for &(from, to, color) in &self.segments {
+            let (a, b) = (to_pixel(from), to_pixel(to));
+            let length = ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt();
+            let steps = (length * 2.0).ceil().max(1.0) as usize;
+            for step in 0..=steps {
+                let t = step as f64 / steps as f64;
+                let p = sub(b, a).map(|d| d * t);
+                let (x, y) = ((a[0] + p[0]) as i64, (a[1] + p[1]) as i64);
+                for dy in -radius..=radius {
+                    for dx in -radius..=radius {
+                        plot(x + dx, y + dy, a[2] + p[2], color);
+                    }
+                }
+            }
+        }
+
+        img
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BACKGROUND: Rgb<u8> = Rgb([255, 255, 255]);
+
+    #[test]
+    fn test_render_box() {
+        let mut scene = Scene::new(BACKGROUND);
+        scene.add_box([0.0, 0.0, 0.0], [1.0, 1.0, 1.0], Rgb([200, 0, 0]));
+        let img = scene.render(&View::default());
+
+        assert_eq!(*img.get_pixel(0, 0), BACKGROUND);
+        let center = img.get_pixel(img.width() / 2, img.height() / 2);
+        assert!(
+            center[0] > 0 && center[1] == 0 && center[2] == 0,
+            "{center:?}"
+        );
+    }
+
+    #[test]
+    fn test_closest_box_is_visible() {
+        let view = View {
+            azimuth_degrees: 0.0,
+            elevation_degrees: 0.0,
+            pixels_per_unit: 10.0,
+        };
+        // Looking along the x axis, the box at x = 5 hides the one at x = 0
+        let mut scene = Scene::new(BACKGROUND);
+        scene.add_box([5.0, 0.0, 0.0], [6.0, 1.0, 1.0], Rgb([0, 200, 0]));
+        scene.add_box([0.0, 0.0, 0.0], [1.0, 1.0, 1.0], Rgb([200, 0, 0]));
+        let img = scene.render(&view);
+        let center = img.get_pixel(img.width() / 2, img.height() / 2);
+        assert!(center[0] == 0 && center[1] > 0, "{center:?}");
+
+        // Segment in front of the boxes is drawn over them
+        scene.add_segment([7.0, 0.0, 0.5], [7.0, 1.0, 0.5], Rgb([0, 0, 255]));
+        let img = scene.render(&view);
+        assert_eq!(
+            *img.get_pixel(img.width() / 2, img.height() / 2),
+            Rgb([0, 0, 255])
+        );
+    }
+
+    #[test]
+    fn test_view_from_straight_above_and_below() {
+        let mut scene = Scene::new(BACKGROUND);
+        scene.add_box([0.0, 0.0, 0.0], [1.0, 1.0, 1.0], Rgb([200, 0, 0]));
+        for elevation_degrees in [90.0, -90.0] {
+            let view = View {
+                elevation_degrees,
+                ..View::default()
+            };
+            let img = scene.render(&view);
+            let center = img.get_pixel(img.width() / 2, img.height() / 2);
+            assert!(
+                center[0] > 0 && center[1] == 0,
+                "{elevation_degrees}: {center:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,363 @@
+use crate::util::svg::escape;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Images bigger than this are listed in the report but not embedded, to keep it openable in a browser.
+const MAX_IMAGE_BYTES: usize = 5_000_000;
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { padding: 2px 10px; text-align: left; border-bottom: 1px solid #ddd; }
+td.answer { font-family: monospace; }
+td.pass { color: #1a7f37; }
+td.fail { color: #cf222e; font-weight: bold; }
+td.unknown { color: #888; }
+td.time { text-align: right; font-family: monospace; }
+.bar { background: #4c78a8; height: 10px; min-width: 1px; }
+figure { display: inline-block; margin: 0 1em 1em 0; vertical-align: top; }
+figure img { max-width: 600px; max-height: 600px; border: 1px solid #ddd; }
+figcaption { font-size: small; color: #555; }";
+
+/// Answers of the known puzzle answers file, per (year, day) the parts and their answers.
+/// Lines are `<year> <day> <part> <answer>`, empty lines and lines starting with '#' are skipped.
+pub fn parse_answers(text: &str) -> BTreeMap<(usize, u8), Vec<(u8, String)>> {
+    let mut answers: BTreeMap<(usize, u8), Vec<(u8, String)>> = BTreeMap::new();
+    for line in text.lines() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.splitn(4, ' ').collect::<Vec<&str>>()[..] {
+            [year, day, part, answer] => answers
+                .entry((year.parse().unwrap(), day.parse().unwrap()))
+                .or_default()
+                .push((part.parse().unwrap(), answer.to_string())),
+            _ => panic!("Invalid line in answers file {:?}", line),
+        }
+    }
+    answers
+}
+
+/// Standard base64 with padding, for data URIs.
+pub fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+// Day that an output file or dir belongs to, named like "day_18_part_1.gif", "day23.png" or "day07"
+fn day_of(name: &str) -> Option<u8> {
+    let rest = name.strip_prefix("day")?;
+    let rest = rest.strip_prefix('_').unwrap_or(rest);
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+fn mime_type(path: &Path) -> Option<&'static str> {
+    match path.extension()?.to_str()? {
+        "png" => Some("image/png"),
+        "gif" => Some("image/gif"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "svg" => Some("image/svg+xml"),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PartResult {
+    year: usize,
+    day: u8,
+    part: u8,
+    answer: String,
+    ms: f64,
+}
+
+#[derive(Debug, Clone)]
+struct Image {
+    name: String,
+    // None when the image is too big to embed
+    data_uri: Option<String>,
+}
+
+/// Self-contained HTML page with the answers and timings of a run, checked against the known answers,
+/// and the images written for the days that ran. Images are embedded as data URIs, so the page can be archived as is.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    parts: Vec<PartResult>,
+    answers: BTreeMap<(usize, u8), Vec<(u8, String)>>,
+    images: BTreeMap<(usize, u8), Vec<Image>>,
+}
+
+impl Report {
+    /// `answers` as returned by `parse_answers`.
+    pub fn new(answers: BTreeMap<(usize, u8), Vec<(u8, String)>>) -> Self {
+        Report {
+            answers,
+            ..Default::default()
+        }
+    }
+
+    pub fn add_part(&mut self, year: usize, day: u8, part: u8, answer: String, ms: f64) {
+        self.parts.push(PartResult {
+            year,
+            day,
+            part,
+            answer,
+            ms,
+        });
+    }
+
+    /// Adds the images in `{dir}/{year}/` for the days in the report so far,
+    /// both files named after the day (`day_18_part_1.png`) and files in a dir per day (`day18/scores.png`).
+    pub fn add_images(&mut self, dir: &Path) {
+        let days: BTreeSet<(usize, u8)> = self.parts.iter().map(|p| (p.year, p.day)).collect();
+        for (year, day) in days {
+            let year_dir = dir.join(year.to_string());
+            let Ok(entries) = fs::read_dir(&year_dir) else {
+                continue;
+            };
+            let mut paths = Vec::new();
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.file_name().and_then(|n| n.to_str()).and_then(day_of) != Some(day) {
+                    continue;
+                }
+                if path.is_dir() {
+                    // Only the files directly in the dir, frames of animations are in sub dirs
+                    paths.extend(
+                        fs::read_dir(&path)
+                            .into_iter()
+                            .flatten()
+                            .flatten()
+                            .map(|e| e.path()),
+                    );
+                } else {
+                    paths.push(path);
+                }
+            }
+            paths.sort();
+
+            let images = self.images.entry((year, day)).or_default();
+            for path in paths {
+                let Some(mime) = mime_type(&path).filter(|_| path.is_file()) else {
+                    continue;
+                };
+                let name = path
+                    .strip_prefix(dir)
+                    .unwrap_or(&path)
+                    .display()
+                    .to_string();
+                if images.iter().any(|image| image.name == name) {
+                    continue;
+                }
+                let bytes = fs::read(&path)
+                    .unwrap_or_else(|e| panic!("Could not read image {:?}: {}", path, e));
+                let data_uri = (bytes.len() <= MAX_IMAGE_BYTES)
+                    .then(|| format!("data:{};base64,{}", mime, base64(&bytes)));
+                images.push(Image { name, data_uri });
+            }
+        }
+    }
+
+    // Known answer of a part, if any
+    fn expected(&self, year: usize, day: u8, part: u8) -> Option<&str> {
+        self.answers
+            .get(&(year, day))?
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, answer)| answer.as_str())
+    }
+
+    pub fn to_html(&self) -> String {
+        let total_ms: f64 = self.parts.iter().map(|p| p.ms).sum();
+        let (mut passed, mut failed) = (0, 0);
+        for p in &self.parts {
+            match self.expected(p.year, p.day, p.part) {
+                Some(expected) if expected == p.answer => passed += 1,
+                Some(_) => failed += 1,
+                None => {}
+            }
+        }
+
+        let mut html = String::new();
+        writeln!(
+            html,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">"
+        )
+        .unwrap();
+        writeln!(
+            html,
+            "<title>Advent of Code report</title>\n<style>\n{}\n</style>",
+            STYLE
+        )
+        .unwrap();
+        writeln!(html, "</head>\n<body>\n<h1>Advent of Code report</h1>").unwrap();
+        writeln!(
+            html,
+            "<p>{} parts in {:.4} ms: {} correct, {} wrong, {} without a known answer</p>",
+            self.parts.len(),
+            total_ms,
+            passed,
+            failed,
+            self.parts.len() - passed - failed
+        )
+        .unwrap();
+
+        let years: BTreeSet<usize> = self.parts.iter().map(|p| p.year).collect();
+        for year in years {
+            let parts: Vec<&PartResult> = self.parts.iter().filter(|p| p.year == year).collect();
+            let year_ms: f64 = parts.iter().map(|p| p.ms).sum();
+            // Bars are relative to the slowest part of the year
+            let max_ms = parts.iter().map(|p| p.ms).fold(0.0, f64::max);
+
+            writeln!(html, "<h2>{}</h2>", year).unwrap();
+            writeln!(html, "<p>{} parts in {:.4} ms</p>", parts.len(), year_ms).unwrap();
+            writeln!(
+                html,
+                "<table>\n<tr><th>Day</th><th>Part</th><th>Answer</th><th>Check</th><th>Time (ms)</th><th></th></tr>"
+            )
+            .unwrap();
+            for p in &parts {
+                let (class, check) = match self.expected(p.year, p.day, p.part) {
+                    Some(expected) if expected == p.answer => ("pass", "correct".to_string()),
+                    Some(expected) => ("fail", format!("expected {}", escape(expected))),
+                    None => ("unknown", "no known answer".to_string()),
+                };
+                let width = if max_ms > 0.0 {
+                    p.ms / max_ms * 300.0
+                } else {
+                    0.0
+                };
+                writeln!(
+                    html,
+                    "<tr><td>{:02}</td><td>{}</td><td class=\"answer\">{}</td><td class=\"{}\">{}</td><td class=\"time\">{:.4}</td><td><div class=\"bar\" style=\"width: {:.1}px\"></div></td></tr>",
+                    p.day,
+                    p.part,
+                    escape(&p.answer),
+                    class,
+                    check,
+                    p.ms,
+                    width
+                )
+                .unwrap();
+            }
+            writeln!(html, "</table>").unwrap();
+
+            for ((_, day), images) in self.images.range((year, 0)..=(year, u8::MAX)) {
+                if images.is_empty() {
+                    continue;
+                }
+                writeln!(html, "<h3>Day {:02}</h3>", day).unwrap();
+                for image in images {
+                    match &image.data_uri {
+                        Some(data_uri) => writeln!(
+                            html,
+                            "<figure><img src=\"{}\" alt=\"{}\"><figcaption>{}</figcaption></figure>",
+                            data_uri,
+                            escape(&image.name),
+                            escape(&image.name)
+                        ),
+                        None => writeln!(
+                            html,
+                            "<figure><figcaption>{} (too big to embed)</figcaption></figure>",
+                            escape(&image.name)
+                        ),
+                    }
+                    .unwrap();
+                }
+            }
+        }
+
+        writeln!(html, "</body>\n</html>").unwrap();
+        html
+    }
+
+    pub fn write(&self, path: &Path) {
+        fs::write(path, self.to_html())
+            .unwrap_or_else(|e| panic!("Could not write report {:?}: {}", path, e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64(&[0xff, 0xfe, 0x00]), "//4A");
+    }
+
+    #[test]
+    fn test_day_of() {
+        assert_eq!(day_of("day_18_part_1.gif"), Some(18));
+        assert_eq!(day_of("day23.png"), Some(23));
+        assert_eq!(day_of("day07"), Some(7));
+        assert_eq!(day_of("day_5"), Some(5));
+        assert_eq!(day_of("days.txt"), None);
+        assert_eq!(day_of("scores.png"), None);
+    }
+
+    #[test]
+    fn test_report() {
+        let answers = parse_answers("# comment\n2024 1 1 11\n2024 1 2 31\n\n2024 2 1 2\n");
+        assert_eq!(
+            answers[&(2024, 1)],
+            vec![(1, "11".to_string()), (2, "31".to_string())]
+        );
+
+        let mut report = Report::new(answers);
+        report.add_part(2024, 1, 1, "11".to_string(), 2.0);
+        report.add_part(2024, 1, 2, "<30>".to_string(), 1.0);
+        report.add_part(2024, 3, 1, "7".to_string(), 0.5);
+
+        let root = std::env::temp_dir().join(format!("aoc_report_{}", std::process::id()));
+        fs::create_dir_all(root.join("2024").join("day01")).unwrap();
+        fs::write(root.join("2024").join("day_1.png"), b"png").unwrap();
+        fs::write(root.join("2024").join("day01").join("grid.svg"), b"<svg/>").unwrap();
+        fs::write(root.join("2024").join("day01").join("grid.txt"), b"#").unwrap();
+        fs::write(root.join("2024").join("day_12.png"), b"png").unwrap();
+        report.add_images(&root);
+        fs::remove_dir_all(&root).unwrap();
+
+        let html = report.to_html();
+        assert!(html
+            .contains("<p>3 parts in 3.5000 ms: 1 correct, 1 wrong, 1 without a known answer</p>"));
+        assert!(html
+            .contains("<td class=\"answer\">&lt;30&gt;</td><td class=\"fail\">expected 31</td>"));
+        assert!(html.contains("style=\"width: 300.0px\""));
+        assert!(html.contains("<img src=\"data:image/png;base64,cG5n\""));
+        assert!(html.contains("<img src=\"data:image/svg+xml;base64,PHN2Zy8+\""));
+        assert_eq!(html.matches("<figure>").count(), 2);
+        assert!(!html.contains("http"));
+
+        // Every year gets one section, in order, whatever order the parts were solved in
+        let mut report = Report::new(BTreeMap::new());
+        report.add_part(2024, 1, 1, "1".to_string(), 1.0);
+        report.add_part(2023, 1, 1, "2".to_string(), 1.0);
+        report.add_part(2024, 2, 1, "3".to_string(), 1.0);
+        let html = report.to_html();
+        assert_eq!(html.matches("<h2>2024</h2>").count(), 1);
+        assert!(html.find("<h2>2023</h2>").unwrap() < html.find("<h2>2024</h2>").unwrap());
+    }
+}
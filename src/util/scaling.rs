@@ -0,0 +1,178 @@
+/// Runtime of a solver on an input of the given size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    pub size: usize,
+    pub ms: f64,
+}
+
+/// Sizes growing geometrically from `min` to `max` (both included), with `steps` sizes in total.
+pub fn geometric_sizes(min: usize, max: usize, steps: usize) -> Vec<usize> {
+    if steps < 2 || min >= max {
+        return vec![max];
+    }
+    let ratio = (max as f64 / min as f64).powf(1.0 / (steps - 1) as f64);
+    let mut sizes: Vec<usize> = (0..steps)
+        .map(|i| (min as f64 * ratio.powi(i as i32)).round() as usize)
+        .collect();
+    sizes.dedup();
+    sizes
+}
+
+/// Exponent k of the best fitting `time = c * size^k`, with least squares on the log-log points.
+/// Returns None when there are fewer than 2 distinct sizes to fit on.
+pub fn fit_exponent(measurements: &[Measurement]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = measurements
+        .iter()
+        .filter(|m| m.size > 0 && m.ms > 0.0)
+        .map(|m| ((m.size as f64).ln(), m.ms.ln()))
+        .collect();
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+    let covariance: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+    let variance: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+
+    if variance == 0.0 {
+        None
+    } else {
+        Some(covariance / variance)
+    }
+}
+
+/// Closest simple complexity class for an exponent, e.g. 1.93 -> "O(n^2)".
+pub fn complexity_class(exponent: f64) -> String {
+    let rounded = (exponent * 2.0).round() / 2.0;
+    if rounded <= 0.0 {
+        "O(1)".to_string()
+    } else if rounded == 1.0 {
+        "O(n)".to_string()
+    } else {
+        format!("O(n^{})", rounded)
+    }
+}
+
+/// Table with the time per size, and how much slower each size was than the previous one.
+pub fn table(measurements: &[Measurement]) -> String {
+    let mut lines = vec![format!(
+        "{:>10}  {:>12}  {:>7}",
+        "size", "time (ms)", "growth"
+    )];
+    let mut previous: Option<&Measurement> = None;
+    for m in measurements {
+        let growth = match previous {
+            Some(p) if p.ms > 0.0 => format!("x{:.2}", m.ms / p.ms),
+            _ => String::new(),
+        };
+        lines.push(format!("{:>10}  {:>12.4}  {:>7}", m.size, m.ms, growth));
+        previous = Some(m);
+    }
+    lines.join("\n")
+}
+
+/// Log-log scatter plot of time against size. A straight line means polynomial growth,
+/// its slope is the exponent.
+pub fn plot(measurements: &[Measurement], width: usize, height: usize) -> String {
+    let points: Vec<(f64, f64)> = measurements
+        .iter()
+        .filter(|m| m.size > 0 && m.ms > 0.0)
+        .map(|m| ((m.size as f64).ln(), m.ms.ln()))
+        .collect();
+    if points.is_empty() || width < 2 || height < 2 {
+        return String::new();
+    }
+    let bounds = |values: Vec<f64>| {
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        (min, if max > min { max } else { min + 1.0 })
+    };
+    let (x_min, x_max) = bounds(points.iter().map(|p| p.0).collect());
+    let (y_min, y_max) = bounds(points.iter().map(|p| p.1).collect());
+
+    let mut grid = vec![vec![' '; width]; height];
+    for (x, y) in points {
+        let column = ((x - x_min) / (x_max - x_min) * (width - 1) as f64).round() as usize;
+        let row = ((y_max - y) / (y_max - y_min) * (height - 1) as f64).round() as usize;
+        grid[row][column] = '*';
+    }
+
+    let label_max = format!("{:.3} ms", y_max.exp());
+    let label_min = format!("{:.3} ms", y_min.exp());
+    let label_width = label_max.len().max(label_min.len());
+    let mut lines: Vec<String> = grid
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let label = match i {
+                0 => &label_max,
+                i if i == height - 1 => &label_min,
+                _ => "",
+            };
+            format!(
+                "{:>label_width$} |{}",
+                label,
+                row.into_iter().collect::<String>()
+            )
+        })
+        .collect();
+    lines.push(format!("{:>label_width$} +{}", "", "-".repeat(width)));
+    let size_min = x_min.exp().round().to_string();
+    let size_max = x_max.exp().round().to_string();
+    lines.push(format!(
+        "{:>label_width$}  {}{:>right$}",
+        "",
+        size_min,
+        size_max,
+        right = width.saturating_sub(size_min.len())
+    ));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measure(sizes: &[usize], time: impl Fn(f64) -> f64) -> Vec<Measurement> {
+        sizes
+            .iter()
+            .map(|&size| Measurement {
+                size,
+                ms: time(size as f64),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fit_exponent() {
+        let sizes = geometric_sizes(100, 10_000, 5);
+        assert_eq!(sizes, [100, 316, 1000, 3162, 10000]);
+
+        let quadratic = fit_exponent(&measure(&sizes, |n| 0.001 * n * n)).unwrap();
+        assert!((quadratic - 2.0).abs() < 1e-3, "{quadratic}");
+        let n_log_n = fit_exponent(&measure(&sizes, |n| n * n.ln())).unwrap();
+        assert!(1.0 < n_log_n && n_log_n < 1.3, "{n_log_n}");
+        assert_eq!(complexity_class(quadratic), "O(n^2)");
+        assert_eq!(complexity_class(n_log_n), "O(n)");
+        assert_eq!(complexity_class(1.4), "O(n^1.5)");
+
+        assert_eq!(fit_exponent(&measure(&[100, 100], |n| n)), None);
+    }
+
+    #[test]
+    fn test_plot() {
+        let measurements = measure(&[1, 10, 100], |n| n * n);
+        assert_eq!(
+            plot(&measurements, 5, 3),
+            [
+                "10000.000 ms |    *",
+                "             |  *  ",
+                "    1.000 ms |*    ",
+                "             +-----",
+                "              1 100",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use crate::util::terminal::TerminalAnimation;
+use crate::util::visualize::Visualizer;
+use rand::rngs::StdRng;
 use std::fmt::{Display, Formatter, Result};
 use Solution::*;
 
@@ -20,6 +23,15 @@ pub enum Solution {
 
 pub type SolverFn = fn(&str) -> Solution;
 
+/// Generates a random puzzle input of the given size.
+pub type GeneratorFn = fn(&mut StdRng, usize) -> String;
+
+/// Solves the input again while writing visual output of the solution.
+pub type VisualizerFn = fn(&str, &mut Visualizer);
+
+/// Solves the input again while playing the simulation in the terminal.
+pub type AnimationFn = fn(&str, &mut TerminalAnimation);
+
 #[macro_export]
 macro_rules! make_solver {
     ($year:ident, $day:ident) => {
@@ -0,0 +1,226 @@
+use std::fmt::{Display, Formatter};
+
+/// Point in SVG coordinates, y points down.
+pub type Point = (f64, f64);
+
+/// Minimal SVG document builder. Colors are anything SVG understands, e.g. "red", "#ff0000" or "#ff000080".
+/// Line widths are in pixels, also when the drawing is scaled, see `fitted`.
+#[derive(Debug, Clone)]
+pub struct Svg {
+    width: f64,
+    height: f64,
+    view_box: (f64, f64, f64, f64),
+    elements: Vec<String>,
+    arrows: bool,
+}
+
+/// Hex notation of a color, e.g. "#ff0000".
+pub fn hex(rgb: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2])
+}
+
+/// Escapes the characters that have a meaning in XML, also usable for HTML.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn points_attribute(points: &[Point]) -> String {
+    points
+        .iter()
+        .map(|(x, y)| format!("{:.2},{:.2}", x, y))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+impl Svg {
+    pub fn new(width: f64, height: f64) -> Self {
+        Svg {
+            width,
+            height,
+            view_box: (0.0, 0.0, width, height),
+            elements: Vec::new(),
+            arrows: false,
+        }
+    }
+
+    /// Drawing in the original coordinates between the `min` and `max` corners, with a margin around it.
+    /// The viewer scales it to at most `max_pixels` wide or high, so puzzle coordinates can be used as they are.
+    pub fn fitted(min: Point, max: Point, max_pixels: f64) -> Self {
+        let (width, height) = ((max.0 - min.0).max(1.0), (max.1 - min.1).max(1.0));
+        let margin = width.max(height) * 0.02;
+        let view_box = (
+            min.0 - margin,
+            min.1 - margin,
+            width + 2.0 * margin,
+            height + 2.0 * margin,
+        );
+        let scale = max_pixels / view_box.2.max(view_box.3);
+        Svg {
+            width: (view_box.2 * scale).round(),
+            height: (view_box.3 * scale).round(),
+            view_box,
+            elements: Vec::new(),
+            arrows: false,
+        }
+    }
+
+    /// Size of a pixel in drawing coordinates, to size points and text of fitted drawings.
+    pub fn pixel(&self) -> f64 {
+        self.view_box.2 / self.width
+    }
+
+    pub fn background(&mut self, color: &str) {
+        let (x, y, width, height) = self.view_box;
+        self.rect((x, y), (width, height), color);
+    }
+
+    pub fn rect(&mut self, corner: Point, size: (f64, f64), fill: &str) {
+        self.elements.push(format!(
+            r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="{}"/>"#,
+            corner.0, corner.1, size.0, size.1, fill
+        ));
+    }
+
+    /// Rectangle between two opposite corners with an outline, `stroke_width` 0 for no outline.
+    pub fn rectangle(&mut self, a: Point, b: Point, fill: &str, stroke: &str, stroke_width: f64) {
+        self.elements.push(format!(
+            r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="{}" stroke="{}" stroke-width="{}" vector-effect="non-scaling-stroke"/>"#,
+            a.0.min(b.0),
+            a.1.min(b.1),
+            (a.0 - b.0).abs(),
+            (a.1 - b.1).abs(),
+            fill,
+            stroke,
+            stroke_width
+        ));
+    }
+
+    pub fn line(&mut self, from: Point, to: Point, stroke: &str, width: f64) {
+        self.elements.push(format!(
+            r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="{}" stroke-width="{}" vector-effect="non-scaling-stroke"/>"#,
+            from.0, from.1, to.0, to.1, stroke, width
+        ));
+    }
+
+    /// Line with an arrow head at `to`.
+    pub fn arrow(&mut self, from: Point, to: Point, stroke: &str, width: f64) {
+        self.arrows = true;
+        self.elements.push(format!(
+            r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" stroke="{}" stroke-width="{}" vector-effect="non-scaling-stroke" marker-end="url(#arrow)"/>"#,
+            from.0, from.1, to.0, to.1, stroke, width
+        ));
+    }
+
+    pub fn circle(&mut self, center: Point, radius: f64, fill: &str) {
+        self.elements.push(format!(
+            r#"<circle cx="{:.2}" cy="{:.2}" r="{:.2}" fill="{}"/>"#,
+            center.0, center.1, radius, fill
+        ));
+    }
+
+    /// Closed polygon, `stroke_width` 0 for no outline.
+    pub fn polygon(&mut self, points: &[Point], fill: &str, stroke: &str, stroke_width: f64) {
+        self.elements.push(format!(
+            r#"<polygon points="{}" fill="{}" stroke="{}" stroke-width="{}" vector-effect="non-scaling-stroke"/>"#,
+            points_attribute(points),
+            fill,
+            stroke,
+            stroke_width
+        ));
+    }
+
+    /// Text centered on the position.
+    pub fn text(&mut self, position: Point, text: &str, size: f64, fill: &str) {
+        self.elements.push(format!(
+            r#"<text x="{:.2}" y="{:.2}" font-size="{}" font-family="monospace" text-anchor="middle" dominant-baseline="middle" fill="{}">{}</text>"#,
+            position.0,
+            position.1,
+            size,
+            fill,
+            escape(text)
+        ));
+    }
+
+    /// Hover text for the element added last.
+    pub fn title(&mut self, title: &str) {
+        if let Some(element) = self.elements.last_mut() {
+            if let Some(stripped) = element.strip_suffix("/>") {
+                *element = format!("{}><title>{}</title></{}>", stripped, escape(title), {
+                    let tag = &stripped[1..];
+                    tag.split(' ').next().unwrap_or(tag).to_string()
+                });
+            }
+        }
+    }
+}
+
+impl Display for Svg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+            self.width,
+            self.height,
+            self.view_box.0,
+            self.view_box.1,
+            self.view_box.2,
+            self.view_box.3
+        )?;
+        if self.arrows {
+            writeln!(
+                f,
+                r#"<defs><marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="6" markerHeight="6" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="context-stroke"/></marker></defs>"#
+            )?;
+        }
+        for element in &self.elements {
+            writeln!(f, "{}", element)?;
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg() {
+        let mut svg = Svg::new(100.0, 50.0);
+        svg.circle((10.0, 20.0), 3.0, "red");
+        svg.title("a < b");
+        svg.text((50.0, 25.0), "x & y", 12.0, "black");
+        assert_eq!(
+            svg.to_string(),
+            [
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50" viewBox="0 0 100 50">"#,
+                r#"<circle cx="10.00" cy="20.00" r="3.00" fill="red"><title>a &lt; b</title></circle>"#,
+                r#"<text x="50.00" y="25.00" font-size="12" font-family="monospace" text-anchor="middle" dominant-baseline="middle" fill="black">x &amp; y</text>"#,
+                "</svg>\n",
+            ]
+            .join("\n")
+        );
+
+        let mut svg = Svg::new(10.0, 10.0);
+        svg.arrow((0.0, 0.0), (5.0, 5.0), "black", 1.0);
+        assert!(svg.to_string().contains(r#"<marker id="arrow""#));
+    }
+
+    #[test]
+    fn test_fitted() {
+        // 1000 x 500 units with a margin of 20 on every side, shown at most 104 pixels wide
+        let mut svg = Svg::fitted((0.0, 0.0), (1000.0, 500.0), 104.0);
+        assert_eq!((svg.width, svg.height), (104.0, 54.0));
+        assert_eq!(svg.pixel(), 10.0);
+        svg.rectangle((10.0, 20.0), (0.0, 0.0), &hex([255, 0, 128]), "none", 0.0);
+        assert_eq!(
+            svg.to_string().lines().take(2).collect::<Vec<&str>>(),
+            [
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="104" height="54" viewBox="-20 -20 1040 540">"#,
+                r##"<rect x="0.00" y="0.00" width="10.00" height="20.00" fill="#ff0080" stroke="none" stroke-width="0" vector-effect="non-scaling-stroke"/>"##,
+            ]
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use crate::util::gif::Palette;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const CURSOR_HOME: &str = "\x1b[H";
+const CLEAR_TO_END: &str = "\x1b[J";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const RESET_COLOR: &str = "\x1b[0m";
+
+// Range of speeds that +/- can reach, beyond it a frame is not visible or the animation seems stuck
+const MIN_FRAME_TIME: Duration = Duration::from_millis(1);
+const MAX_FRAME_TIME: Duration = Duration::from_secs(10);
+
+/// What can be typed while an animation runs, every command is followed by Enter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    TogglePause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+fn parse_control(line: &str) -> Option<Control> {
+    match line.trim() {
+        "" | "p" => Some(Control::TogglePause),
+        "s" | "n" => Some(Control::Step),
+        "+" => Some(Control::Faster),
+        "-" => Some(Control::Slower),
+        "q" => Some(Control::Quit),
+        _ => None,
+    }
+}
+
+// Stdin is read by a single thread for the whole program, so animations of several days can share it.
+// None when stdin is not a terminal, then animations just play.
+fn controls() -> Option<&'static Mutex<Receiver<Control>>> {
+    static CONTROLS: OnceLock<Option<Mutex<Receiver<Control>>>> = OnceLock::new();
+    CONTROLS
+        .get_or_init(|| {
+            if !io::stdin().is_terminal() {
+                return None;
+            }
+            let (sender, receiver) = mpsc::channel();
+            thread::spawn(move || {
+                for line in io::stdin().lock().lines().map_while(Result::ok) {
+                    if let Some(control) = parse_control(&line) {
+                        if sender.send(control).is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+            Some(Mutex::new(receiver))
+        })
+        .as_ref()
+}
+
+/// Grid with every character that has a color in the palette drawn in that color, using 24-bit ANSI escape codes.
+/// Characters without a color keep the default color of the terminal.
+pub fn render_ansi(grid: &str, palette: &Palette) -> String {
+    let mut out = String::with_capacity(grid.len() * 2);
+    for line in grid.lines() {
+        let mut current = None;
+        for c in line.chars() {
+            let color = palette.get(c);
+            if color != current {
+                match color {
+                    Some(rgb) => {
+                        let [r, g, b] = rgb.0;
+                        out.push_str(&format!("\x1b[38;2;{};{};{}m", r, g, b));
+                    }
+                    None => out.push_str(RESET_COLOR),
+                }
+                current = color;
+            }
+            out.push(c);
+        }
+        if current.is_some() {
+            out.push_str(RESET_COLOR);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn clamp_frame_time(frame_time: Duration) -> Duration {
+    frame_time.clamp(MIN_FRAME_TIME, MAX_FRAME_TIME)
+}
+
+/// Plays the states of a grid simulation in the terminal, redrawing the grid in place.
+/// While it plays, Enter pauses and resumes, `s` steps one frame while paused, `+`/`-` change the speed
+/// and `q` skips to the end.
+pub struct TerminalAnimation {
+    palette: Palette,
+    frame_time: Duration,
+    every: usize,
+    offered: usize,
+    frames_drawn: usize,
+    paused: bool,
+    stopped: bool,
+    last_grid: String,
+}
+
+impl TerminalAnimation {
+    pub fn new(fps: f64) -> Self {
+        TerminalAnimation {
+            palette: Palette::new(&[]),
+            frame_time: clamp_frame_time(Duration::from_secs_f64(1.0 / fps.max(0.1))),
+            every: 1,
+            offered: 0,
+            frames_drawn: 0,
+            paused: false,
+            stopped: false,
+            last_grid: String::new(),
+        }
+    }
+
+    /// Colors of the grid characters, set by the day being animated.
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    /// Only draw every n-th state, for simulations with too many steps to watch.
+    pub fn set_every(&mut self, n: usize) {
+        self.every = n.max(1);
+    }
+
+    /// True once `q` was typed, simulations can stop producing frames.
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// Show the next state, `grid` is only called to draw it for the states that are shown.
+    pub fn frame_with(&mut self, grid: impl FnOnce() -> String) {
+        if !self.stopped && self.offered.is_multiple_of(self.every) {
+            self.draw(&grid());
+            self.wait();
+        }
+        self.offered += 1;
+    }
+
+    /// Draw the end state even if it would be skipped or the animation was stopped.
+    pub fn finish(&mut self, grid: &str) {
+        self.draw(grid);
+    }
+
+    fn draw(&mut self, grid: &str) {
+        let mut out = io::stdout().lock();
+        if self.frames_drawn == 0 {
+            write!(out, "{}{}", CLEAR_SCREEN, HIDE_CURSOR).unwrap();
+        }
+        self.frames_drawn += 1;
+        let status = if self.paused {
+            "paused - Enter: resume, s: step, q: skip to end"
+        } else {
+            "Enter: pause, +/-: speed, q: skip to end"
+        };
+        writeln!(
+            out,
+            "{}{}{}frame {} at {:.0} fps - {}",
+            CURSOR_HOME,
+            render_ansi(grid, &self.palette),
+            CLEAR_TO_END,
+            self.offered,
+            1.0 / self.frame_time.as_secs_f64(),
+            status
+        )
+        .unwrap();
+        out.flush().unwrap();
+        if self.last_grid != grid {
+            self.last_grid = grid.to_string();
+        }
+    }
+
+    // Wait for the next frame, handling the controls typed in the meantime
+    fn wait(&mut self) {
+        let deadline = Instant::now() + self.frame_time;
+        loop {
+            let timeout = if self.paused {
+                Duration::from_millis(100)
+            } else {
+                deadline.saturating_duration_since(Instant::now())
+            };
+            if timeout.is_zero() {
+                return;
+            }
+            let Some(controls) = controls() else {
+                thread::sleep(timeout);
+                continue;
+            };
+            match controls.lock().unwrap().recv_timeout(timeout) {
+                Ok(Control::TogglePause) => {
+                    self.paused = !self.paused;
+                    // Redraw to update the status line
+                    let grid = std::mem::take(&mut self.last_grid);
+                    self.draw(&grid);
+                }
+                Ok(Control::Step) if self.paused => return,
+                Ok(Control::Step) => {}
+                Ok(Control::Faster) => self.frame_time = clamp_frame_time(self.frame_time / 2),
+                Ok(Control::Slower) => self.frame_time = clamp_frame_time(self.frame_time * 2),
+                Ok(Control::Quit) => {
+                    self.stopped = true;
+                    self.paused = false;
+                    return;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    // Stdin was closed, so nothing can resume a paused animation anymore
+                    self.paused = false;
+                    thread::sleep(deadline.saturating_duration_since(Instant::now()));
+                }
+            }
+        }
+    }
+}
+
+impl Drop for TerminalAnimation {
+    fn drop(&mut self) {
+        if self.frames_drawn > 0 {
+            print!("{}", SHOW_CURSOR);
+            io::stdout().flush().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_ansi() {
+        let palette = Palette::new(&[('#', [255, 0, 0]), ('@', [0, 0, 255])]);
+        assert_eq!(
+            render_ansi("##.\n.@@", &palette),
+            "\x1b[38;2;255;0;0m##\x1b[0m.\n.\x1b[38;2;0;0;255m@@\x1b[0m\n"
+        );
+        assert_eq!(render_ansi("...", &palette), "...\n");
+    }
+
+    #[test]
+    fn test_parse_control() {
+        assert_eq!(parse_control("\n"), Some(Control::TogglePause));
+        assert_eq!(parse_control("s"), Some(Control::Step));
+        assert_eq!(parse_control(" q "), Some(Control::Quit));
+        assert_eq!(parse_control("x"), None);
+    }
+
+    #[test]
+    fn test_frame_time_is_clamped() {
+        assert_eq!(TerminalAnimation::new(1e12).frame_time, MIN_FRAME_TIME);
+        assert_eq!(TerminalAnimation::new(0.0).frame_time, MAX_FRAME_TIME);
+        assert_eq!(
+            TerminalAnimation::new(20.0).frame_time,
+            Duration::from_millis(50)
+        );
+        assert_eq!(clamp_frame_time(MIN_FRAME_TIME / 2), MIN_FRAME_TIME);
+        assert_eq!(clamp_frame_time(MAX_FRAME_TIME * 2), MAX_FRAME_TIME);
+    }
+}
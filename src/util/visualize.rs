@@ -0,0 +1,155 @@
+use crate::util::gif::GifRecorder;
+use crate::util::graph::Graph;
+use crate::util::svg::Svg;
+use image::RgbImage;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+/// Sink for visual output of a day: text renders, images, graphs and numbered frames.
+/// Everything is written to `{root}/{year}/day{NN}/`, see `--visualize` in `main.rs`.
+/// Solvers take a disabled visualizer when they only need the answer, which makes every call a no-op.
+pub struct Visualizer {
+    dir: Option<PathBuf>,
+    frame_counts: HashMap<String, usize>,
+}
+
+impl Visualizer {
+    pub fn new(root: &Path, year: usize, day: u8) -> Self {
+        Visualizer {
+            dir: Some(root.join(year.to_string()).join(format!("day{:02}", day))),
+            frame_counts: HashMap::new(),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Visualizer {
+            dir: None,
+            frame_counts: HashMap::new(),
+        }
+    }
+
+    /// Check this before doing extra work that is only needed for the visualization.
+    pub fn is_enabled(&self) -> bool {
+        self.dir.is_some()
+    }
+
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    // Path of a file in the output dir, creating the dirs on the way
+    fn path(&self, file_name: &str) -> Option<PathBuf> {
+        let path = self.dir.as_ref()?.join(file_name);
+        let parent = path.parent().expect("Output path has no parent");
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|e| panic!("Could not create output dir {:?}: {}", parent, e));
+        Some(path)
+    }
+
+    fn write(&self, file_name: &str, contents: &str) {
+        if let Some(path) = self.path(file_name) {
+            fs::write(&path, contents)
+                .unwrap_or_else(|e| panic!("Could not write {:?}: {}", path, e));
+        }
+    }
+
+    fn save(&self, file_name: &str, image: &RgbImage) {
+        if let Some(path) = self.path(file_name) {
+            image
+                .save(&path)
+                .unwrap_or_else(|e| panic!("Could not save image {:?}: {}", path, e));
+        }
+    }
+
+    // Next file name in the sequence of frames with this name
+    fn next_frame(&mut self, name: &str, extension: &str) -> String {
+        let count = self.frame_counts.entry(name.to_string()).or_default();
+        let file_name = format!("{}/{:05}.{}", name, count, extension);
+        *count += 1;
+        file_name
+    }
+
+    /// Writes `{name}.txt`.
+    pub fn text(&mut self, name: &str, contents: &str) {
+        self.write(&format!("{}.txt", name), contents);
+    }
+
+    /// Writes `{name}.png`.
+    pub fn image(&mut self, name: &str, image: &RgbImage) {
+        self.save(&format!("{}.png", name), image);
+    }
+
+    /// Writes `{name}.dot` -> render with `dot -Kneato -Tpng -o {name}.png {name}.dot`.
+    pub fn graph(&mut self, name: &str, graph: &Graph) {
+        self.write(&format!("{}.dot", name), &graph.to_dot());
+    }
+
+    /// Writes `{name}.dot` as given, for diagrams with styling or positions that `Graph` does not have.
+    pub fn dot(&mut self, name: &str, dot: &str) {
+        self.write(&format!("{}.dot", name), dot);
+    }
+
+    /// Writes `{name}.svg`, see `layout::graph_to_svg` for graphs.
+    pub fn svg(&mut self, name: &str, svg: &Svg) {
+        self.write(&format!("{}.svg", name), &svg.to_string());
+    }
+
+    /// Writes the recorded frames as `{name}.gif`.
+    pub fn gif(&mut self, name: &str, recorder: &GifRecorder) {
+        if let Some(path) = self.path(&format!("{}.gif", name)) {
+            let file = File::create(&path)
+                .unwrap_or_else(|e| panic!("Could not create {:?}: {}", path, e));
+            recorder
+                .encode(BufWriter::new(file))
+                .unwrap_or_else(|e| panic!("Could not write GIF {:?}: {}", path, e));
+        }
+    }
+
+    /// Adds the next frame of an animation as `{name}/{NNNNN}.png`.
+    pub fn frame(&mut self, name: &str, image: &RgbImage) {
+        if self.is_enabled() {
+            let file_name = self.next_frame(name, "png");
+            self.save(&file_name, image);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    #[test]
+    fn test_visualizer() {
+        let root = std::env::temp_dir().join(format!("aoc_visualize_{}", std::process::id()));
+        let mut visualizer = Visualizer::new(&root, 2024, 7);
+        let day_dir = root.join("2024").join("day07");
+        assert_eq!(visualizer.dir(), Some(day_dir.as_path()));
+
+        visualizer.text("grid", "#.#\n");
+        visualizer.frame("pixels", &RgbImage::from_pixel(2, 2, Rgb([255, 0, 0])));
+        visualizer.frame("pixels", &RgbImage::from_pixel(2, 2, Rgb([0, 0, 255])));
+        let mut graph = Graph::undirected();
+        graph.add_edge("a", "b");
+        visualizer.graph("network", &graph);
+
+        assert_eq!(
+            fs::read_to_string(day_dir.join("grid.txt")).unwrap(),
+            "#.#\n"
+        );
+        assert!(day_dir.join("pixels/00000.png").exists());
+        assert!(day_dir.join("pixels/00001.png").exists());
+        assert!(fs::read_to_string(day_dir.join("network.dot"))
+            .unwrap()
+            .contains("\"a\" -- \"b\""));
+        fs::remove_dir_all(root).unwrap();
+
+        // Disabled visualizer writes nothing
+        let mut disabled = Visualizer::disabled();
+        disabled.frame("pixels", &RgbImage::from_pixel(2, 2, Rgb([255, 0, 0])));
+        assert!(!disabled.is_enabled());
+        assert!(disabled.frame_counts.is_empty());
+    }
+}
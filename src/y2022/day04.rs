@@ -1,19 +1,22 @@
-fn parse_pairs(line: &str) -> Vec<i32> {
-    line.split(|c| c == ',' || c == '-')
-        .collect::<Vec<&str>>()
-        .iter()
-        .map(|v| v.parse::<i32>().unwrap())
-        .collect()
+use crate::util::parse::ints;
+use crate::util::ranges::RangeSet;
+
+// Both section assignments of a pair, inclusive on both ends
+fn parse_pairs(line: &str) -> (RangeSet<i32>, RangeSet<i32>) {
+    let sections: Vec<i32> = ints(line).collect();
+    (
+        std::iter::once(sections[0]..sections[1] + 1).collect(),
+        std::iter::once(sections[2]..sections[3] + 1).collect(),
+    )
 }
 
 // In how many assignment pairs does one range fully contain the other?
 pub fn part_1(input: &str) -> i32 {
     input.lines().fold(0, |acc, line| {
-        let pairs = parse_pairs(line);
+        let (left, right) = parse_pairs(line);
 
-        let left_contains_right: bool = pairs[0] <= pairs[2] && pairs[1] >= pairs[3];
-        let right_contains_left: bool = pairs[0] >= pairs[2] && pairs[1] <= pairs[3];
-        acc + if left_contains_right || right_contains_left {
+        let common = left.intersection(&right);
+        acc + if common == left || common == right {
             1
         } else {
             0
@@ -24,9 +27,9 @@ pub fn part_1(input: &str) -> i32 {
 // In how many assignment pairs do the ranges overlap?
 pub fn part_2(input: &str) -> i32 {
     input.lines().fold(0, |acc, line| {
-        let pairs = parse_pairs(line);
+        let (left, right) = parse_pairs(line);
 
-        let overlap: bool = pairs[1] >= pairs[2] && pairs[0] <= pairs[3];
+        let overlap: bool = left.intersection(&right).len() > 0;
         acc + if overlap { 1 } else { 0 }
     })
 }
@@ -1,70 +1,23 @@
-use std::ops::Range;
+use crate::util::ranges::{RangeMap, RangeSet};
 use std::str::Lines;
-use std::{cmp, usize};
 
-struct Map {
-    // List of source range mapping to destination starting point.
-    // If x fits in a range, it gets mapped to the destination + x - source_range.start
-    mappings: Vec<(Range<usize>, usize)>,
-}
-
-impl Map {
-    fn map(&self, x: &usize) -> usize {
-        for (range, destination) in self.mappings.iter() {
-            if range.contains(&(x - 1)) {
-                return destination + x - range.start;
-            }
-        }
-
-        *x
-    }
-
-    fn from_lines(lines: Lines) -> Self {
-        let mut mappings: Vec<(Range<usize>, usize)> = lines
-            .skip(1)
-            .map(|line| {
-                match line
-                    .split_ascii_whitespace()
-                    .map(|s| s.parse::<usize>().unwrap())
-                    .collect::<Vec<usize>>()[..]
-                {
-                    [destination_start, source_start, length] => {
-                        (source_start..source_start + length - 1, destination_start)
-                    }
-                    _ => panic!("Invalid line {:?}", line),
-                }
-            })
-            .collect();
-        mappings.sort_by_key(|(source, _)| source.start);
-
-        Map { mappings }
-    }
-
-    fn map_range(&self, range: Range<usize>) -> Vec<Range<usize>> {
-        let mut result = Vec::new();
-        let relevant_mappings: Vec<&(Range<usize>, usize)> = self
-            .mappings
-            .iter()
-            .filter(|(source, _)| source.start <= range.end && source.end >= range.start)
-            .collect();
-
-        if relevant_mappings.len() == 0 {
-            return vec![range];
-        }
-
-        for (i, (source, destination)) in relevant_mappings.iter().enumerate() {
-            if i == 0 && range.start < source.start {
-                result.push(range.start..source.start - 1);
-            }
-            let new_range_start = cmp::max(range.start, source.start) + destination - source.start;
-            let new_range_end = cmp::min(range.end, source.end) + destination - source.start;
-            result.push(new_range_start..new_range_end);
-            if i == relevant_mappings.len() - 1 && source.end < range.end {
-                result.push(source.end + 1..range.end);
+// Each line maps the source range starting at `source_start` onto the range starting at
+// `destination_start`. Numbers that are not in any source range map onto themselves.
+fn parse_map(lines: Lines) -> RangeMap<usize> {
+    let mut map = RangeMap::new();
+    lines.skip(1).for_each(|line| {
+        match line
+            .split_ascii_whitespace()
+            .map(|s| s.parse::<usize>().unwrap())
+            .collect::<Vec<usize>>()[..]
+        {
+            [destination_start, source_start, length] => {
+                map.add(source_start..source_start + length, destination_start)
             }
+            _ => panic!("Invalid line {:?}", line),
         }
-        result
-    }
+    });
+    map
 }
 
 fn parse_seeds(splits: &str) -> Vec<usize> {
@@ -75,19 +28,21 @@ fn parse_seeds(splits: &str) -> Vec<usize> {
         .collect()
 }
 
-fn parse_maps(splits: std::str::Split<'_, &str>) -> Vec<Map> {
-    splits.map(|map| Map::from_lines(map.lines())).collect()
+// Compose the maps of all categories into a single seed-to-location map
+fn parse_maps(splits: std::str::Split<'_, &str>) -> RangeMap<usize> {
+    splits
+        .map(|map| parse_map(map.lines()))
+        .fold(RangeMap::new(), |chain, m| chain.compose(&m))
 }
 
 pub fn part_1(input: &str) -> usize {
     let mut splits = input.split("\n\n");
     let seeds: Vec<usize> = parse_seeds(splits.next().unwrap());
-    let maps: Vec<Map> = parse_maps(splits);
+    let seed_to_location = parse_maps(splits);
 
-    *maps
-        .iter()
-        .fold(seeds, |seeds, m| seeds.iter().map(|x| m.map(x)).collect())
-        .iter()
+    seeds
+        .into_iter()
+        .map(|seed| seed_to_location.map(seed))
         .min()
         .unwrap()
 }
@@ -95,23 +50,13 @@ pub fn part_1(input: &str) -> usize {
 pub fn part_2(input: &str) -> usize {
     let mut splits = input.split("\n\n");
     let seeds: Vec<usize> = parse_seeds(splits.next().unwrap());
-    let seed_ranges: Vec<Range<usize>> = seeds
+    let seed_ranges: RangeSet<usize> = seeds
         .chunks_exact(2)
-        .map(|seeds| (seeds[0]..seeds[0] + seeds[1] - 1))
+        .map(|seeds| seeds[0]..seeds[0] + seeds[1])
         .collect();
-    let maps: Vec<Map> = parse_maps(splits);
-
-    maps.iter()
-        .fold(seed_ranges, |seed_ranges, m| {
-            seed_ranges
-                .into_iter()
-                .flat_map(|range| m.map_range(range))
-                .collect()
-        })
-        .iter()
-        .map(|range| range.start)
-        .min()
-        .unwrap()
+    let seed_to_location = parse_maps(splits);
+
+    seed_to_location.map_set(&seed_ranges).min().unwrap()
 }
 
 #[cfg(test)]
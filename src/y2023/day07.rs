@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
 use std::str::Lines;
 use std::usize;
 
@@ -152,6 +154,25 @@ pub fn part_2(input: &str) -> usize {
     calculate_result(hands)
 }
 
+// Random list of `size` different hands with their bets
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let cards: Vec<char> = "AKQJT98765432".chars().collect();
+    // There are only 13^5 different hands
+    let size = size.min(cards.len().pow(5));
+    let mut hands: HashSet<String> = HashSet::new();
+    let mut lines = Vec::with_capacity(size);
+
+    while lines.len() < size {
+        let hand: String = (0..5)
+            .map(|_| cards[rng.gen_range(0..cards.len())])
+            .collect();
+        if hands.insert(hand.clone()) {
+            lines.push(format!("{} {}", hand, rng.gen_range(1..=1000)));
+        }
+    }
+    lines.join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,7 +118,9 @@ pub fn part_2(input: &str) -> usize {
 
 #[cfg(test)]
 mod tests {
+    use crate::util::differential::{assert_agree, shrink_number, shrink_vec};
     use crate::util::util::load_input;
+    use rand::Rng;
 
     use super::*;
 
@@ -174,4 +176,56 @@ XXX = (XXX, XXX)";
     fn test_part_2() {
         assert_eq!(part_2(&load_input("inputs/2023/day_8")), 14299763833181);
     }
+
+    // The LCM solution assumes each ghost reaches its Z node after some nr of steps and then loops back to it
+    // in exactly that many steps. Generate networks with that shape: one loop per ghost with the given length.
+    fn ghost_network(instructions: &[bool], loop_lengths: &[usize]) -> String {
+        let mut lines = vec![
+            instructions
+                .iter()
+                .map(|&left| if left { 'L' } else { 'R' })
+                .collect::<String>(),
+            String::new(),
+        ];
+        for (ghost, &length) in loop_lengths.iter().enumerate() {
+            let name = |suffix: &str| format!("{}{}", ghost, suffix);
+            let mut path = vec![name("AA")];
+            path.extend((1..length).map(|i| name(&format!("{}B", i))));
+            path.push(name("ZZ"));
+            for (i, node) in path.iter().enumerate() {
+                let next = path.get(i + 1).unwrap_or(&path[1]);
+                lines.push(format!("{} = ({}, {})", node, next, next));
+            }
+        }
+        lines.join("\n")
+    }
+
+    #[test]
+    fn test_part_2_differential() {
+        assert_agree(
+            100,
+            |rng| {
+                let instructions = (0..rng.gen_range(1..5)).map(|_| rng.gen()).collect();
+                let loop_lengths = (0..rng.gen_range(1..4))
+                    .map(|_| rng.gen_range(1..10))
+                    .collect();
+                (instructions, loop_lengths)
+            },
+            |(instructions, loop_lengths): &(Vec<bool>, Vec<usize>)| {
+                let shorter_instructions = shrink_vec(instructions, |_| vec![])
+                    .into_iter()
+                    .filter(|i| !i.is_empty())
+                    .map(|i| (i, loop_lengths.clone()));
+                let shorter_loops = shrink_vec(loop_lengths, |&n| shrink_number(n))
+                    .into_iter()
+                    .filter(|l| !l.is_empty() && !l.contains(&0))
+                    .map(|l| (instructions.clone(), l));
+                shorter_instructions.chain(shorter_loops).collect()
+            },
+            |(instructions, loop_lengths)| {
+                part_2_bruteforce(&ghost_network(instructions, loop_lengths))
+            },
+            |(instructions, loop_lengths)| part_2(&ghost_network(instructions, loop_lengths)),
+        );
+    }
 }
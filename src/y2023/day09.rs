@@ -1,4 +1,4 @@
-use std::str::Lines;
+use crate::util::poly;
 
 enum Direction {
     Backward,
@@ -11,18 +11,14 @@ fn parse_line(line: &str) -> Vec<i32> {
         .collect()
 }
 
+// The sequence is generated by a polynomial -> Newton forward differences give the next/previous value
 fn extrapolate(nums: Vec<i32>, direction: &Direction) -> i32 {
-    if nums.iter().sum::<i32>() == 0 {
-        return 0;
-    }
-
-    let diff: Vec<i32> = nums.windows(2).map(|w| w[1] - w[0]).collect();
-    let extrapolated = extrapolate(diff, direction);
-
-    match direction {
-        Direction::Backward => nums.first().unwrap() - extrapolated,
-        Direction::Forward => extrapolated + nums.last().unwrap(),
-    }
+    let sequence: Vec<i128> = nums.iter().map(|&x| x as i128).collect();
+    let index = match direction {
+        Direction::Backward => -1,
+        Direction::Forward => sequence.len() as i128,
+    };
+    poly::extrapolate(&sequence, index) as i32
 }
 
 pub fn part_1(input: &str) -> i32 {
@@ -1,3 +1,4 @@
+use crate::util::polygon;
 use core::fmt;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::str::{FromStr, Lines};
@@ -100,15 +101,6 @@ fn find_start(network: &HashMap<Position, Tile>) -> &Position {
         .unwrap()
 }
 
-fn neighbors_basic(current_pos: &Position) -> Vec<Position> {
-    vec![
-        (current_pos.0 + 1, current_pos.1),
-        (current_pos.0 - 1, current_pos.1),
-        (current_pos.0, current_pos.1 + 1),
-        (current_pos.0, current_pos.1 - 1),
-    ]
-}
-
 fn neighbors_network(network: &HashMap<Position, Tile>, position: &Position) -> Vec<Position> {
     let mut out = Vec::new();
 
@@ -222,136 +214,47 @@ pub fn part_1(input: &str) -> i32 {
     max_steps
 }
 
-// Insert ground between everything so that I don't have to handle special cases in the flood fill
-fn add_ground(network: HashMap<(i32, i32), Tile>) -> HashMap<(i32, i32), Tile> {
-    let mut out = HashMap::new();
-
-    // Each tile gets moved into a 2x2 block
-    let (x, y) = network.keys().max_by_key(|pos| *pos).unwrap();
-    for i in 0..=*x {
-        for j in 0..=*y {
-            let tile = network.get(&(i, j)).unwrap();
-            // The tile itself goes bottom right
-            out.insert((2 * i, 2 * j), *tile);
-            // Top left is always Ground
-            out.insert((2 * i - 1, 2 * j - 1), Tile::Ground);
-
-            // Top right, and bottom left depend on tile type
-            let (top_right, bottom_left) = match tile {
-                Tile::Start => (
-                    match network.get(&(i - 1, j)).unwrap_or(&Tile::Ground) {
-                        Tile::Vertical
-                        | Tile::Bend(BendConnections::SouthAndWest)
-                        | Tile::Bend(BendConnections::SouthAndEast) => Tile::Vertical,
-                        _ => Tile::Ground,
-                    },
-                    match network.get(&(i, j - 1)).unwrap_or(&Tile::Ground) {
-                        Tile::Horizontal
-                        | Tile::Bend(BendConnections::NorthAndEast)
-                        | Tile::Bend(BendConnections::SouthAndEast) => Tile::Horizontal,
-                        _ => Tile::Ground,
-                    },
-                ),
-                Tile::Ground => (Tile::Ground, Tile::Ground),
-                Tile::Vertical => (Tile::Vertical, Tile::Ground),
-                Tile::Horizontal => (Tile::Ground, Tile::Horizontal),
-                Tile::Bend(connection) => match connection {
-                    BendConnections::NorthAndEast => (Tile::Vertical, Tile::Ground),
-                    BendConnections::NorthAndWest => (Tile::Vertical, Tile::Horizontal),
-                    BendConnections::SouthAndEast => (Tile::Ground, Tile::Ground),
-                    BendConnections::SouthAndWest => (Tile::Ground, Tile::Horizontal),
-                },
-            };
-
-            out.insert((2 * i - 1, 2 * j), top_right);
-            out.insert((2 * i, 2 * j - 1), bottom_left);
-        }
-    }
-
-    out
-}
-
-fn find_cycle(network: &HashMap<Position, Tile>, start: &Position) -> HashSet<Position> {
-    let mut frontier: VecDeque<Position> = VecDeque::from([start.clone()]);
-    let mut visited: HashSet<Position> = HashSet::new();
-
-    while let Some(current_pos) = frontier.pop_front() {
-        if !visited.contains(&current_pos) {
-            visited.insert(current_pos.clone());
-            for next_pos in neighbors_network(&network, &current_pos) {
-                if !visited.contains(&next_pos) && network.contains_key(&next_pos) {
-                    frontier.push_back(next_pos);
-                }
+// The loop as its positions in order, walking from the start through the pipes.
+// Pipes next to the start can point at it without being part of the loop, those walks end in a pipe that does not connect.
+fn find_loop(network: &HashMap<Position, Tile>, start: &Position) -> Vec<Position> {
+    'walk: for first in neighbors_network(network, start) {
+        let mut path = vec![*start];
+        let (mut previous, mut current) = (*start, first);
+        while current != *start {
+            if !network.contains_key(&current) {
+                continue 'walk;
             }
-        }
-    }
-
-    visited
-}
-
-fn flood_fill(network: &HashMap<Position, Tile>, cycle: &HashSet<Position>) -> HashSet<Position> {
-    let mut frontier: VecDeque<Position> = VecDeque::new();
-    let mut visited: HashSet<Position> = HashSet::new();
-
-    // fill frontier with edges of the map
-    let (x, y) = network.keys().max_by_key(|pos| *pos).unwrap();
-    let left_and_right_edges = (0..=*x).into_iter().flat_map(|i| [(i, 0), (i, *y)]);
-    let top_and_bottom_edges = (0..=*y).into_iter().flat_map(|j| [(0, j), (*x, j)]);
-    left_and_right_edges
-        .chain(top_and_bottom_edges)
-        .filter(|pos| !cycle.contains(pos))
-        .for_each(|pos| frontier.push_back(pos));
-
-    while let Some(current_pos) = frontier.pop_front() {
-        if !visited.contains(&current_pos) {
-            visited.insert(current_pos);
-
-            for next_pos in neighbors_basic(&current_pos) {
-                if !visited.contains(&next_pos)
-                    && network.contains_key(&next_pos)
-                    && !cycle.contains(&next_pos)
-                {
-                    frontier.push_back(next_pos);
-                }
+            let next = neighbors_network(network, &current);
+            if !next.contains(&previous) {
+                continue 'walk;
             }
+            path.push(current);
+            let following = next.into_iter().find(|&p| p != previous).unwrap();
+            (previous, current) = (current, following);
         }
+        return path;
     }
-
-    visited
+    panic!("No loop through the start position")
 }
 
 // Find the number of tiles enclosed by the loop that contains the start position
 pub fn part_2(input: &str) -> usize {
-    let original_network = parse_network(input);
-    // print_network(&original_network, None);
-
-    let network = add_ground(original_network);
-    // print_network(&network, None);
-
+    let network = parse_network(input);
     let start = find_start(&network);
-    let cycle: HashSet<Position> = find_cycle(&network, start);
-    // print_network(&network, Some(&cycle));
-
-    let flood: HashSet<Position> = flood_fill(&network, &cycle);
-    // print_network(&network, Some(&flood));
-
-    let remaining: HashSet<Position> = network
-        .keys()
-        .cloned()
-        .collect::<HashSet<Position>>()
-        .difference(&cycle.union(&flood).cloned().collect())
-        .cloned()
-        .collect();
-    // print_network(&network, Some(&remaining));
+    let pipe_loop = find_loop(&network, start);
+    let on_loop: HashSet<&Position> = pipe_loop.iter().collect();
 
-    let remaining_from_original: HashSet<Position> = remaining
+    // The loop is a polygon with a corner on every bend, tiles that are not part of it are inside or outside
+    let corners: Vec<polygon::Point> = pipe_loop
         .iter()
-        .filter(|(x, y)| x % 2 == 0 && y % 2 == 0)
-        .cloned()
+        .filter(|p| !matches!(network[*p], Tile::Vertical | Tile::Horizontal))
+        .map(|&(x, y)| (x as i64, y as i64))
         .collect();
-    // print_network(&network, Some(&remaining_from_original));
-
-    remaining_from_original.len()
+    network
+        .keys()
+        .filter(|p| !on_loop.contains(p))
+        .filter(|&&(x, y)| polygon::contains_nonzero(&corners, (x as i64, y as i64)))
+        .count()
 }
 
 #[cfg(test)]
@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use crate::util::gif::{GifRecorder, Palette};
+use crate::util::visualize::Visualizer;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use std::str::Lines;
 use std::usize;
@@ -31,6 +33,13 @@ enum Direction {
     West,
 }
 
+const DIRECTIONS_CYCLE: [Direction; 4] = [
+    Direction::North,
+    Direction::West,
+    Direction::South,
+    Direction::East,
+];
+
 fn parse_grid(input: &str) -> Grid {
     let grid: Grid = input
         .lines()
@@ -47,13 +56,11 @@ fn parse_grid(input: &str) -> Grid {
     grid
 }
 
-#[allow(dead_code)]
-fn print_grid(grid: &Grid) {
-    grid.iter().for_each(|row| {
-        row.iter().for_each(|c| print!("{c}"));
-        println!("");
-    });
-    println!("");
+fn grid_to_string(grid: &Grid) -> String {
+    grid.iter()
+        .map(|row| row.iter().map(|c| c.to_string()).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
 fn tilt(grid: &Grid, direction: Direction) -> Grid {
@@ -146,13 +153,6 @@ pub fn part_2(input: &str) -> usize {
     let mut grid = parse_grid(input);
     // Keep track of when a repeating grid was last seen
     let mut cache: HashMap<Grid, (Grid, usize)> = HashMap::new();
-    let directions_cycle = [
-        Direction::North,
-        Direction::West,
-        Direction::South,
-        Direction::East,
-    ];
-
     let nr_of_cycles = 1_000_000_000;
     for i in 0..nr_of_cycles {
         if let Some((final_grid, seen_at)) = cache.get(&grid) {
@@ -164,7 +164,7 @@ pub fn part_2(input: &str) -> usize {
         }
 
         let old_grid = grid.clone();
-        for &direction in directions_cycle.iter() {
+        for direction in DIRECTIONS_CYCLE {
             grid = tilt(&grid, direction);
         }
 
@@ -174,6 +174,24 @@ pub fn part_2(input: &str) -> usize {
     total_load(&grid)
 }
 
+// Animation of the spin cycles, every tilt is a frame, until the rocks end up in a state they were in before
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let palette =
+        Palette::new(&[('O', [230, 200, 80]), ('#', [110, 110, 110])]).background([20, 20, 30]);
+    let mut recorder = GifRecorder::new(palette).cell_size(4).delay_ms(100);
+    let mut grid = parse_grid(input);
+    recorder.record_with(|| grid_to_string(&grid));
+
+    let mut seen: HashSet<Grid> = HashSet::new();
+    while seen.insert(grid.clone()) {
+        for direction in DIRECTIONS_CYCLE {
+            grid = tilt(&grid, direction);
+            recorder.record_with(|| grid_to_string(&grid));
+        }
+    }
+    visualizer.gif("spin_cycles", &recorder);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,5 @@
+use crate::util::gif::Palette;
+use crate::util::terminal::TerminalAnimation;
 use std::collections::HashSet;
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
@@ -8,21 +10,27 @@ enum Direction {
     Right,
 }
 
-#[allow(dead_code)]
-fn print_grid(grid: &Vec<u8>, width: usize, energized: &HashSet<&usize>, current_beam: usize) {
+fn grid_to_string(
+    grid: &[u8],
+    width: usize,
+    energized: &HashSet<usize>,
+    current_beam: usize,
+) -> String {
+    let mut s = String::with_capacity(grid.len() * 2);
     for row in 0..grid.len() / width {
         for col in 0..width {
             let index = row * width + col;
             if index == current_beam {
-                print!("@");
+                s.push('@');
             } else if grid[index] == b'.' && energized.contains(&index) {
-                print!("█");
+                s.push('█');
             } else {
-                print!("{}", grid[index] as char);
+                s.push(grid[index] as char);
             }
         }
-        println!("");
+        s.push('\n');
     }
+    s
 }
 
 // Determine the next directions for a beam on the current cell in the grid. Beams can split in two directions.
@@ -50,20 +58,22 @@ fn next_position(i: usize, direction: Direction, width: usize, grid_len: usize)
     }
 }
 
-fn count_energized_tiles(grid: &Vec<u8>, width: usize, initial_beam: (usize, Direction)) -> usize {
+fn count_energized_tiles(
+    grid: &Vec<u8>,
+    width: usize,
+    initial_beam: (usize, Direction),
+    mut animation: Option<&mut TerminalAnimation>,
+) -> usize {
     let mut beams: Vec<(usize, Direction)> = Vec::from([initial_beam]);
     let mut visited: HashSet<(usize, Direction)> = HashSet::new();
-    // let mut iters = 0;
+    let mut energized_so_far: HashSet<usize> = HashSet::new();
 
     while let Some(beam) = beams.pop() {
         visited.insert(beam);
-        // if iters % 20 == 0 {
-        //     print!("\x1B[2J\x1b[1;1H");
-        //     println!("Beam at {:?} going {:?}", (beam.0 / width, beam.0 % width), beam.1);
-        //     print_grid(&grid, width, &visited.iter().map(|(i, _)| i).collect(), beam.0);
-        //     thread::sleep(Duration::from_millis(100));
-        // }
-        // iters += 1;
+        if let Some(animation) = animation.as_deref_mut().filter(|a| !a.is_stopped()) {
+            energized_so_far.insert(beam.0);
+            animation.frame_with(|| grid_to_string(grid, width, &energized_so_far, beam.0));
+        }
 
         next_directions(grid[beam.0], beam.1).iter().for_each(|d| {
             if let Some(next_direction) = *d {
@@ -78,7 +88,10 @@ fn count_energized_tiles(grid: &Vec<u8>, width: usize, initial_beam: (usize, Dir
         })
     }
 
-    let energized: HashSet<&usize> = visited.iter().map(|(i, _)| i).collect();
+    let energized: HashSet<usize> = visited.iter().map(|(i, _)| *i).collect();
+    if let Some(animation) = animation {
+        animation.finish(&grid_to_string(grid, width, &energized, usize::MAX));
+    }
     energized.len()
 }
 
@@ -87,7 +100,7 @@ pub fn part_1(input: &str) -> usize {
     let mut lines = input.lines().peekable();
     let width = lines.peek().unwrap().len();
     let grid: Vec<u8> = lines.flat_map(|line| line.as_bytes().to_vec()).collect();
-    count_energized_tiles(&grid, width, (0, Direction::Right))
+    count_energized_tiles(&grid, width, (0, Direction::Right), None)
 }
 
 // Find the beam on the edges of the grid that energizes the largest number of tiles and return that number.
@@ -110,11 +123,29 @@ pub fn part_2(input: &str) -> usize {
 
     initial_beams
         .into_iter()
-        .map(|beam| count_energized_tiles(&grid, width, beam))
+        .map(|beam| count_energized_tiles(&grid, width, beam, None))
         .max()
         .unwrap()
 }
 
+// Beam of part 1 spreading through the contraption
+pub fn animate(input: &str, animation: &mut TerminalAnimation) {
+    let mut lines = input.lines().peekable();
+    let width = lines.peek().unwrap().len();
+    let grid: Vec<u8> = lines.flat_map(|line| line.as_bytes().to_vec()).collect();
+    animation.set_palette(Palette::new(&[
+        ('█', [255, 200, 40]),
+        ('@', [255, 60, 60]),
+        ('|', [90, 160, 255]),
+        ('-', [90, 160, 255]),
+        ('/', [90, 160, 255]),
+        ('\\', [90, 160, 255]),
+    ]));
+    // Draw the spreading front rather than every single step on real inputs
+    animation.set_every(grid.len() / 1000 + 1);
+    count_energized_tiles(&grid, width, (0, Direction::Right), Some(animation));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,6 +167,13 @@ mod tests {
         assert_eq!(part_1(EXAMPLE_INPUT_1), 46);
     }
 
+    #[test]
+    fn test_grid_to_string() {
+        let grid = b"..|.\\...".to_vec();
+        let energized = HashSet::from([0, 1, 2, 5]);
+        assert_eq!(grid_to_string(&grid, 4, &energized, 1), "█@|.\n\\█..\n");
+    }
+
     #[test]
     fn test_part_1() {
         assert_eq!(part_1(&load_input("inputs/2023/day_16")), 7199);
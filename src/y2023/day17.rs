@@ -1,4 +1,6 @@
-use std::collections::{BinaryHeap, HashSet};
+use crate::util::heatmap::Heatmap;
+use crate::util::visualize::Visualizer;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::str::Lines;
 use std::usize;
 
@@ -133,13 +135,22 @@ impl PartialOrd for Move {
     }
 }
 
-fn dijkstra<FN1, FN2, IN>(grid: &mut Grid, success: FN1, successors: FN2) -> usize
+// Position, direction and nr of steps in that direction
+type State = (usize, Direction, i32);
+
+fn dijkstra<FN1, FN2, IN>(
+    grid: &mut Grid,
+    success: FN1,
+    successors: FN2,
+    visualizer: &mut Visualizer,
+    name: &str,
+) -> usize
 where
     FN1: Fn(&Move) -> bool,
     FN2: Fn(&Grid, &Move) -> IN,
     IN: IntoIterator<Item = Move>,
 {
-    let mut visited: HashSet<(usize, Direction, i32)> = HashSet::new();
+    let mut visited: HashSet<State> = HashSet::new();
     let mut frontier: BinaryHeap<Move> = BinaryHeap::from([
         Move {
             cost: 0,
@@ -154,42 +165,85 @@ where
             steps_in_direction: 0,
         },
     ]);
+    // When visualizing, the search goes on past the goal to get the cost of every cell,
+    // and remembers the cheapest way into each state to follow the path back from the goal.
+    let mut heatmap = Heatmap::new(grid.width, grid.height);
+    let mut parents: HashMap<State, (usize, State)> = HashMap::new();
+    let mut result: Option<(usize, State)> = None;
 
     while let Some(current) = frontier.pop() {
-        if success(&current) {
-            return current.cost;
-        }
-
-        if visited.insert((
+        let state = (
             current.position,
             current.direction,
             current.steps_in_direction,
-        )) {
-            successors(grid, &current)
-                .into_iter()
-                .for_each(|next| frontier.push(next));
+        );
+        if result.is_none() && success(&current) {
+            if !visualizer.is_enabled() {
+                return current.cost;
+            }
+            result = Some((current.cost, state));
+        }
+
+        if visited.insert(state) {
+            if visualizer.is_enabled() {
+                heatmap.record(current.position, current.cost);
+            }
+            successors(grid, &current).into_iter().for_each(|next| {
+                if visualizer.is_enabled() {
+                    let next_state = (next.position, next.direction, next.steps_in_direction);
+                    if parents
+                        .get(&next_state)
+                        .is_none_or(|(cost, _)| next.cost < *cost)
+                    {
+                        parents.insert(next_state, (next.cost, state));
+                    }
+                }
+                frontier.push(next)
+            });
         }
     }
 
-    panic!("Oh no!")
+    let (cost, goal) = result.expect("Oh no!");
+    let mut path = vec![goal.0];
+    let mut state = goal;
+    while let Some(&(_, parent)) = parents.get(&state) {
+        path.push(parent.0);
+        state = parent;
+    }
+    let cell_size = (1000 / grid.width).clamp(2, 20) as u32;
+    visualizer.image(name, &heatmap.render(&path, cell_size));
+    cost
 }
 
-pub fn part_1(input: &str) -> usize {
+// The crucible can move at least `min_steps` and at most `max_steps` in a direction before it has to turn
+fn minimize_heat_loss(
+    input: &str,
+    min_steps: i32,
+    max_steps: i32,
+    visualizer: &mut Visualizer,
+    name: &str,
+) -> usize {
     let mut grid = Grid::from_lines(input.lines());
     let goal = grid.grid.len() - 1;
-    let success = |current: &Move| current.position == goal;
-    let successors = |grid: &Grid, m: &Move| m.next_moves(grid, 0, 3);
+    let success =
+        |current: &Move| current.position == goal && current.steps_in_direction >= min_steps;
+    let successors = |grid: &Grid, m: &Move| m.next_moves(grid, min_steps, max_steps);
+
+    dijkstra(&mut grid, success, successors, visualizer, name)
+}
 
-    dijkstra(&mut grid, success, successors)
+pub fn part_1(input: &str) -> usize {
+    minimize_heat_loss(input, 0, 3, &mut Visualizer::disabled(), "")
 }
 
 pub fn part_2(input: &str) -> usize {
-    let mut grid = Grid::from_lines(input.lines());
-    let goal = grid.grid.len() - 1;
-    let success = |current: &Move| current.position == goal && current.steps_in_direction >= 4;
-    let successors = |grid: &Grid, m: &Move| m.next_moves(grid, 4, 10);
+    minimize_heat_loss(input, 4, 10, &mut Visualizer::disabled(), "")
+}
 
-    dijkstra(&mut grid, success, successors)
+// Lowest heat loss to reach every block, with the best path of both parts on top
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    minimize_heat_loss(input, 0, 3, visualizer, "part_1_heat_loss");
+    minimize_heat_loss(input, 4, 10, visualizer, "part_2_heat_loss");
 }
 
 #[cfg(test)]
@@ -1,3 +1,6 @@
+use crate::util::polygon;
+use crate::util::svg::{self, Svg};
+use crate::util::visualize::Visualizer;
 use image::{ImageBuffer, Rgb, RgbImage};
 use std::collections::HashSet;
 use std::str::Lines;
@@ -250,10 +253,19 @@ impl Edge {
             color: self.color,
         }
     }
+
+    fn reverse(&self) -> Self {
+        Edge {
+            from: self.to,
+            to: self.from,
+            direction: self.direction.opposite(),
+            distance: self.distance,
+            color: self.color,
+        }
+    }
 }
 
-#[allow(dead_code)]
-fn draw_lagoon(edges: &Vec<Edge>, rectangles: &Vec<(Edge, Edge)>) {
+fn draw_lagoon(edges: &[Edge], rectangles: &[(Edge, Edge)]) -> RgbImage {
     let (width, height): (u32, u32) = (800, 800);
     let (x_min, x_max, y_min, y_max) = edges
         .iter()
@@ -314,17 +326,62 @@ fn draw_lagoon(edges: &Vec<Edge>, rectangles: &Vec<(Edge, Edge)>) {
             draw_line(&mut img, x0, y0, x1, y1, edge.color);
         }
     }
-    // img.save(format!("outputs/day_18/day_18_{}.png", rectangles.len()))
-    // img.save("outputs/day_18.png")
-    //     .expect("Failed to save image");
+    img
+}
 
-    // let mut input = String::new();
-    // std::io::stdin()
-    //     .read_line(&mut input)
-    //     .expect("can not read user input");
+// Dig plan in its original coordinates, every cube a unit square, so it stays sharp at any zoom level.
+// The trench runs through the centers of the cubes, sliced off rectangles cover whole cubes.
+fn lagoon_to_svg(edges: &[Edge], rectangles: &[(Edge, Edge)]) -> Svg {
+    let points = edges.iter().flat_map(|e| [e.from, e.to]).chain(
+        rectangles
+            .iter()
+            .flat_map(|(e1, e2)| [e1.from, e1.to, e2.from, e2.to]),
+    );
+    let (min, max) = points.fold(
+        (
+            Point::from(i64::MAX, i64::MAX),
+            Point::from(i64::MIN, i64::MIN),
+        ),
+        |(min, max), p| {
+            (
+                Point::from(min.x.min(p.x), min.y.min(p.y)),
+                Point::from(max.x.max(p.x), max.y.max(p.y)),
+            )
+        },
+    );
+    // Points are (row, column) -> x is the column in the drawing
+    let mut svg = Svg::fitted(
+        (min.y as f64, min.x as f64),
+        ((max.y + 1) as f64, (max.x + 1) as f64),
+        1000.0,
+    );
+    svg.background("black");
+    for (e1, e2) in rectangles {
+        let corners = [e1.from, e1.to, e2.from, e2.to];
+        let row_min = corners.iter().map(|p| p.x).min().unwrap();
+        let row_max = corners.iter().map(|p| p.x).max().unwrap();
+        let column_min = corners.iter().map(|p| p.y).min().unwrap();
+        let column_max = corners.iter().map(|p| p.y).max().unwrap();
+        svg.rectangle(
+            (column_min as f64, row_min as f64),
+            ((column_max + 1) as f64, (row_max + 1) as f64),
+            &format!("{}99", svg::hex(e1.color.0)),
+            "white",
+            0.5,
+        );
+    }
+    for edge in edges {
+        let center = |p: Point| (p.y as f64 + 0.5, p.x as f64 + 0.5);
+        svg.line(
+            center(edge.from),
+            center(edge.to),
+            &svg::hex(edge.color.0),
+            2.0,
+        );
+    }
+    svg
 }
 
-#[allow(dead_code)]
 fn draw_line(img: &mut RgbImage, x0: u32, y0: u32, x1: u32, y1: u32, color: Rgb<u8>) {
     for x in x0.min(x1)..=x1.max(x0) {
         for y in y0.min(y1)..=y1.max(y0) {
@@ -405,9 +462,23 @@ fn can_slice(
 // Calculate area by slicing rectangles off of the polygon until only one rectangle remains
 // This whole thing would have been much easier by searching rectangles based on the corner points,
 // but I wanted to make it work using the directions in the given input.
-fn area_of_rectilinear_polygon(mut edges: Vec<Edge>) -> i64 {
+// Every round of slicing is a frame of the visualization, showing the remaining edges and the sliced off rectangles.
+fn area_of_rectilinear_polygon(mut edges: Vec<Edge>, visualizer: &mut Visualizer) -> i64 {
+    let dig_plan = if visualizer.is_enabled() {
+        edges.clone()
+    } else {
+        Vec::new()
+    };
+
+    // Slicing looks for clockwise turns -> walk counterclockwise dig plans in reverse
+    // Points are (row, column), so a clockwise walk has a negative signed area
+    let vertices: Vec<polygon::Point> = edges.iter().map(|e| (e.from.x, e.from.y)).collect();
+    if polygon::signed_double_area(&vertices) > 0 {
+        edges = edges.iter().rev().map(|e| e.reverse()).collect();
+    }
+
     let mut area: i64 = 0;
-    // let mut rectangles: Vec<(Edge, Edge)> = Vec::new();
+    let mut rectangles: Vec<(Edge, Edge)> = Vec::new();
 
     while edges.len() > 4 {
         let nr_of_edges = edges.len();
@@ -444,7 +515,7 @@ fn area_of_rectilinear_polygon(mut edges: Vec<Edge>) -> i64 {
             if current.distance == grandparent.distance {
                 // Clean cut -> connect before & after rectangle by extending the one before
                 area += current.distance * (parent.distance + 1);
-                // rectangles.push((parent, current));
+                rectangles.push((parent, current));
 
                 let next = if i < nr_of_edges - 3 {
                     edges[i + 3]
@@ -473,7 +544,7 @@ fn area_of_rectilinear_polygon(mut edges: Vec<Edge>) -> i64 {
             } else if current.distance < grandparent.distance {
                 // Current edge is shorter
                 area += current.distance * (parent.distance + 1);
-                // rectangles.push((parent, current));
+                rectangles.push((parent, current));
 
                 // shorten grandparent
                 let new_grandparent = grandparent.shrink(current.distance);
@@ -506,7 +577,7 @@ fn area_of_rectilinear_polygon(mut edges: Vec<Edge>) -> i64 {
             } else {
                 // Current edge is longer
                 area += grandparent.distance * (parent.distance + 1);
-                // rectangles.push((parent, grandparent));
+                rectangles.push((parent, grandparent));
 
                 // Extend great grandparent
                 if let Some(great_grandparent) = next_edges.last_mut() {
@@ -527,12 +598,16 @@ fn area_of_rectilinear_polygon(mut edges: Vec<Edge>) -> i64 {
             }
         }
         edges = next_edges;
-        // draw_lagoon(&edges, &rectangles);
+        if visualizer.is_enabled() {
+            visualizer.frame("slicing", &draw_lagoon(&edges, &rectangles));
+        }
     }
 
-    // rectangles.push((edges[0], edges[1]));
-    // rectangles.push((edges[2], edges[3]));
-    // draw_lagoon(&Vec::new(), &rectangles);
+    if visualizer.is_enabled() {
+        rectangles.push((edges[0], edges[1]));
+        rectangles.push((edges[2], edges[3]));
+        visualizer.svg("rectangles", &lagoon_to_svg(&dig_plan, &rectangles));
+    }
 
     area + (edges[0].distance + 1) * (edges[1].distance + 1)
 }
@@ -540,20 +615,38 @@ fn area_of_rectilinear_polygon(mut edges: Vec<Edge>) -> i64 {
 // Calculate the volume of the lagoon formed by the perimeter. Each position is a 1 meter cube.
 pub fn part_1(input: &str) -> i64 {
     let edges = parse_edges(input.lines(), false);
-    // draw_lagoon(&edges, &Vec::new());
-    area_of_rectilinear_polygon(edges)
+    area_of_rectilinear_polygon(edges, &mut Visualizer::disabled())
+}
+
+// The trench runs through the centers of the cubes, so the lagoon is every lattice point inside or on the polygon
+fn area_with_picks_theorem(edges: &[Edge]) -> i64 {
+    let vertices: Vec<polygon::Point> = edges.iter().map(|e| (e.from.x, e.from.y)).collect();
+    polygon::enclosed_points(&vertices)
 }
 
 pub fn part_2(input: &str) -> i64 {
     let edges = parse_edges(input.lines(), true);
-    // draw_lagoon(&edges, &Vec::new());
-    area_of_rectilinear_polygon(edges)
+    area_with_picks_theorem(&edges)
+}
+
+// Draws the dig plans of both parts, and how part 1 slices its lagoon into rectangles
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let edges = parse_edges(input.lines(), false);
+    visualizer.svg("part_1", &lagoon_to_svg(&edges, &[]));
+    area_of_rectilinear_polygon(edges, visualizer);
+    visualizer.svg(
+        "part_2",
+        &lagoon_to_svg(&parse_edges(input.lines(), true), &[]),
+    );
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::differential::{assert_agree, shrink_vec};
     use crate::util::util::load_input;
+    use rand::rngs::StdRng;
+    use rand::Rng;
 
     const EXAMPLE_INPUT_1: &str = "R 6 (#70c710)
 D 5 (#0dc571)
@@ -604,6 +697,26 @@ U 2 (#7a21e3)";
         assert_eq!(part_1(&lines.join("\n")), 62);
     }
 
+    #[test]
+    fn test_part_1_rectangles_counterclockwise() {
+        // Same lagoon as the example, dug the other way around
+        let reversed = EXAMPLE_INPUT_1
+            .lines()
+            .rev()
+            .map(|line| {
+                let opposite = match &line[..1] {
+                    "R" => "L",
+                    "L" => "R",
+                    "D" => "U",
+                    _ => "D",
+                };
+                format!("{}{}", opposite, &line[1..])
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        assert_eq!(part_1(&reversed), 62);
+    }
+
     #[test]
     fn test_part_1_rectangles() {
         assert_eq!(part_1(&load_input("inputs/2023/day_18_shortened")), 5532);
@@ -612,6 +725,17 @@ U 2 (#7a21e3)";
         assert_eq!(part_1(&load_input("inputs/2023/day_18")), 33491);
     }
 
+    #[test]
+    fn test_area_with_picks_theorem() {
+        let edges = parse_edges(EXAMPLE_INPUT_1.lines(), false);
+        assert_eq!(area_with_picks_theorem(&edges), 62);
+        let edges = parse_edges(EXAMPLE_INPUT_1.lines(), true);
+        assert_eq!(
+            area_with_picks_theorem(&edges),
+            area_of_rectilinear_polygon(edges, &mut Visualizer::disabled())
+        );
+    }
+
     #[test]
     fn test_part_2_example() {
         assert_eq!(part_2(EXAMPLE_INPUT_1), 952408144115);
@@ -621,4 +745,92 @@ U 2 (#7a21e3)";
     fn test_part_2() {
         assert_eq!(part_2(&load_input("inputs/2023/day_18")), 87716969654406);
     }
+
+    // Random shape where each column is a range of rows (top, bottom) that overlaps with the previous column,
+    // so that its outline is a loop that never touches itself
+    fn random_columns(rng: &mut StdRng) -> Vec<(i64, i64)> {
+        let mut columns = vec![(0, rng.gen_range(0..5))];
+        for _ in 0..rng.gen_range(0..6) {
+            let (top, bottom) = columns[columns.len() - 1];
+            let new_top = rng.gen_range(top - 3..=bottom);
+            let new_bottom = rng.gen_range(new_top.max(top)..=bottom + 3);
+            columns.push((new_top, new_bottom));
+        }
+        columns
+    }
+
+    fn valid_columns(columns: &[(i64, i64)]) -> bool {
+        !columns.is_empty()
+            && columns.iter().all(|(top, bottom)| top <= bottom)
+            && columns
+                .windows(2)
+                .all(|w| w[0].0.max(w[1].0) <= w[0].1.min(w[1].1))
+    }
+
+    fn shrink_columns(columns: &Vec<(i64, i64)>) -> Vec<Vec<(i64, i64)>> {
+        shrink_vec(columns, |&(top, bottom)| {
+            vec![(top + 1, bottom), (top, bottom - 1)]
+        })
+        .into_iter()
+        .filter(|c| valid_columns(c))
+        .collect()
+    }
+
+    // Dig plan that follows the outline of the columns, clockwise or counterclockwise
+    fn dig_plan(columns: &[(i64, i64)], clockwise: bool) -> String {
+        let mut corners = Vec::new();
+        for (x, &(top, _)) in columns.iter().enumerate() {
+            corners.extend([(x as i64, top), (x as i64 + 1, top)]);
+        }
+        for (x, &(_, bottom)) in columns.iter().enumerate().rev() {
+            corners.extend([(x as i64 + 1, bottom + 1), (x as i64, bottom + 1)]);
+        }
+        if !clockwise {
+            corners.reverse();
+        }
+
+        let mut moves: Vec<(char, i64)> = Vec::new();
+        for (i, from) in corners.iter().enumerate() {
+            let to = corners[(i + 1) % corners.len()];
+            let step = match (to.0 - from.0, to.1 - from.1) {
+                (0, 0) => continue,
+                (dx, 0) if dx > 0 => ('R', dx),
+                (dx, 0) => ('L', -dx),
+                (0, dy) if dy > 0 => ('D', dy),
+                (0, dy) => ('U', -dy),
+                _ => unreachable!(),
+            };
+            match moves.last_mut() {
+                Some((direction, distance)) if *direction == step.0 => *distance += step.1,
+                _ => moves.push(step),
+            }
+        }
+        // The loop can end with the same direction it started with
+        if moves.len() > 1 && moves[0].0 == moves[moves.len() - 1].0 {
+            let (_, distance) = moves.pop().unwrap();
+            moves[0].1 += distance;
+        }
+
+        moves
+            .iter()
+            .map(|(direction, distance)| format!("{direction} {distance} (#70c710)"))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_part_1_differential() {
+        assert_agree(
+            300,
+            |rng| (random_columns(rng), rng.gen_bool(0.5)),
+            |(columns, clockwise)| {
+                shrink_columns(columns)
+                    .into_iter()
+                    .map(|c| (c, *clockwise))
+                    .collect()
+            },
+            |(columns, clockwise)| part_1_flood_fill(&dig_plan(columns, *clockwise)) as i64,
+            |(columns, clockwise)| part_1(&dig_plan(columns, *clockwise)),
+        );
+    }
 }
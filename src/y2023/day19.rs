@@ -1,5 +1,9 @@
+use crate::util::ranges::split_at;
 use std::collections::HashMap;
-use std::usize;
+use std::ops::Range;
+
+// Half-open ranges of possible values for each of the variables [X, M, A, S]
+type PartRanges = [Range<usize>; 4];
 
 #[derive(Debug, Clone, Copy)]
 enum Category {
@@ -142,53 +146,31 @@ impl Rule {
         }
     }
 
-    // For the given ranges (tuples (from, to)) of variables [X, M, A, S], adapt the range
-    // this rule operates on into a variant that passes this rule and one that fails this rule.
+    // For the given ranges of variables [X, M, A, S], adapt the range this rule operates on
+    // into a variant that passes this rule and one that fails this rule.
     fn generate_pass_and_fail_ranges(
         &self,
-        part_ranges: [(usize, usize); 4],
-    ) -> (Option<[(usize, usize); 4]>, Option<[(usize, usize); 4]>) {
-        match self.condition {
-            Condition::LT(category, target) => {
-                let index = category as usize;
-                let (from, to) = part_ranges[index];
-                if target <= from {
-                    // Range starts higher than target -> can only fail
-                    (None, Some(part_ranges))
-                } else if target <= to {
-                    // Target fits in range -> split the range into pass and fail variants
-                    let mut pass = part_ranges.clone();
-                    pass[index] = (from, target - 1);
-                    let mut fail = part_ranges;
-                    fail[index] = (target, to);
-
-                    (Some(pass), Some(fail))
-                } else {
-                    // Whole range is less than target -> can only pass
-                    (Some(part_ranges), None)
-                }
-            }
-            Condition::GT(category, target) => {
-                let index = category as usize;
-                let (from, to) = part_ranges[index];
-                if target < from {
-                    // Range starts higher than target -> can only pass
-                    (Some(part_ranges), None)
-                } else if target < to {
-                    // Target fits in range -> split the range into pass and fail variants
-                    let mut pass = part_ranges.clone();
-                    pass[index] = (target + 1, to);
-                    let mut fail = part_ranges;
-                    fail[index] = (from, target);
-
-                    (Some(pass), Some(fail))
-                } else {
-                    // Whole range is less than target -> can only fail
-                    (None, Some(part_ranges))
-                }
-            }
-            Condition::NOOP => (Some(part_ranges), None),
-        }
+        part_ranges: &PartRanges,
+    ) -> (Option<PartRanges>, Option<PartRanges>) {
+        let (category, pass_below, at) = match self.condition {
+            Condition::LT(category, target) => (category, true, target),
+            Condition::GT(category, target) => (category, false, target + 1),
+            Condition::NOOP => return (Some(part_ranges.clone()), None),
+        };
+        let index = category as usize;
+        let (below, above) = split_at(&part_ranges[index], at);
+        let (pass, fail) = if pass_below {
+            (below, above)
+        } else {
+            (above, below)
+        };
+        let with_range = |range: Range<usize>| {
+            let mut ranges = part_ranges.clone();
+            ranges[index] = range;
+            ranges
+        };
+
+        (pass.map(with_range), fail.map(with_range))
     }
 }
 
@@ -259,18 +241,16 @@ pub fn part_1(input: &str) -> usize {
 
 // Take all possible flows through the workflows, starting from "in", and generate the ranges of
 // parts that eventually get accepted.
-fn generate_possible_part_ranges(workflows: &Workflows) -> Vec<[(usize, usize); 4]> {
-    let mut result: Vec<[(usize, usize); 4]> = Vec::new();
-    let mut candidates: Vec<(String, [(usize, usize); 4])> = Vec::from([(
-        "in".to_string(),
-        [(1, 4000), (1, 4000), (1, 4000), (1, 4000)],
-    )]);
+fn generate_possible_part_ranges(workflows: &Workflows) -> Vec<PartRanges> {
+    let mut result: Vec<PartRanges> = Vec::new();
+    let mut candidates: Vec<(String, PartRanges)> =
+        Vec::from([("in".to_string(), [1..4001, 1..4001, 1..4001, 1..4001])]);
 
     while let Some((workflow_name, mut candidate)) = candidates.pop() {
         let rules = &workflows.get(workflow_name.as_str()).unwrap().rules;
         for rule in rules {
             // For each rule, generate the two branches that would pass and fail the current rule
-            let (pass, fail) = rule.generate_pass_and_fail_ranges(candidate);
+            let (pass, fail) = rule.generate_pass_and_fail_ranges(&candidate);
 
             // Handle the branch that passes the current rule
             if let Some(pass) = pass {
@@ -307,7 +287,7 @@ pub fn part_2(input: &str) -> usize {
 
     generate_possible_part_ranges(&workflows)
         .iter()
-        .map(|r| r.iter().map(|(from, to)| to - from + 1).product::<usize>())
+        .map(|r| r.iter().map(|range| range.len()).product::<usize>())
         .sum()
 }
 
@@ -1,4 +1,7 @@
-use std::collections::{HashMap, VecDeque};
+use crate::util::graph::Graph;
+use crate::util::layout::{force_directed, graph_to_svg, GraphStyle};
+use crate::util::visualize::Visualizer;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
 use std::str::Lines;
 
@@ -269,7 +272,7 @@ fn lcm(a: usize, b: usize) -> usize {
 pub fn part_2(input: &str) -> usize {
     let (mut modules, module_destinations, broadcaster) = parse_input(input);
 
-    /* The broadcaster sends signals to 4 subgraphs of modules (see visualization /outputs/day_20.png).
+    /* The broadcaster sends signals to 4 subgraphs of modules (see `visualize`).
     Each subgraph counts up to a number and sends a pulse to a conjunction when that number is reached.
     Those 4 conjunctions then feed the final conjunction that feeds the sink `rx`.
     -> rx's conjunction will send a low pulse when all 4 subgraphs reach their number
@@ -314,6 +317,60 @@ pub fn part_2(input: &str) -> usize {
     numbers.into_iter().fold(1, |result, num| lcm(result, num))
 }
 
+// Group of a module in the wiring diagram
+const BROADCASTER: usize = 0;
+const FLIP_FLOP: usize = 1;
+const CONJUNCTION: usize = 2;
+const OUTPUT: usize = 3;
+
+// Wiring of the modules with the group of every module, modules that are only a destination are outputs
+fn parse_wiring(input: &str) -> (Graph, Vec<usize>) {
+    let mut graph = Graph::directed();
+    let mut groups: HashMap<usize, usize> = HashMap::new();
+    for line in input.lines() {
+        let (module, destinations) = line.split_once(" -> ").expect("Invalid line");
+        let (name, group) = match module.split_at(1) {
+            ("%", name) => (name, FLIP_FLOP),
+            ("&", name) => (name, CONJUNCTION),
+            _ => (module, BROADCASTER),
+        };
+        groups.insert(graph.add_node(name), group);
+        for destination in destinations.split(", ") {
+            graph.add_edge(name, destination);
+        }
+    }
+    let groups = (0..graph.len())
+        .map(|id| groups.get(&id).copied().unwrap_or(OUTPUT))
+        .collect();
+    (graph, groups)
+}
+
+// Wiring diagram colored by module type. The edges out of the broadcaster show where the subgraphs
+// start, the edges into the conjunction feeding the output show where they end, see part 2.
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let (graph, groups) = parse_wiring(input);
+    let final_conjunctions: HashSet<usize> = graph
+        .edges()
+        .filter(|&(_, to)| groups[to] == OUTPUT)
+        .map(|(from, _)| from)
+        .collect();
+    let highlighted_edges = graph
+        .edges()
+        .filter(|&(from, to)| {
+            groups[from] == BROADCASTER
+                || final_conjunctions.contains(&to)
+                || final_conjunctions.contains(&from)
+        })
+        .collect();
+    let style = GraphStyle {
+        groups,
+        highlighted_edges,
+        labels: true,
+    };
+    let positions = force_directed(&graph, 500, 20);
+    visualizer.svg("wiring", &graph_to_svg(&graph, &positions, &style, 1000.0));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -341,6 +398,18 @@ mod tests {
         assert_eq!(part_1(EXAMPLE_INPUT_2), 11_687_500);
     }
 
+    #[test]
+    fn test_parse_wiring() {
+        let (graph, groups) = parse_wiring(EXAMPLE_INPUT_2);
+        let id = |name: &str| graph.id(name).unwrap();
+        assert_eq!(graph.len(), 6);
+        assert_eq!(groups[id("broadcaster")], BROADCASTER);
+        assert_eq!(groups[id("a")], FLIP_FLOP);
+        assert_eq!(groups[id("con")], CONJUNCTION);
+        assert_eq!(groups[id("output")], OUTPUT);
+        assert_eq!(graph.neighbors(id("a")), [id("inv"), id("con")]);
+    }
+
     #[test]
     fn test_part_1() {
         assert_eq!(part_1(&load_input("inputs/2023/day_20")), 912_199_500);
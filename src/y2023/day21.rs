@@ -1,3 +1,5 @@
+use crate::util::poly::{self, lagrange, Polynomial};
+use crate::util::visualize::Visualizer;
 use std::collections::HashSet;
 use std::usize;
 
@@ -126,23 +128,11 @@ pub fn part_1(input: &str) -> usize {
     part_1_iterations(input, 64)
 }
 
-fn second_order_lagrange_polynomial(x_i: Vec<f64>, y_i: Vec<f64>) -> impl Fn(f64) -> f64 {
-    move |new_x| -> f64 {
-        let term_1 = y_i[0]
-            * ((new_x - x_i[1]) / (x_i[0] - x_i[1]))
-            * ((new_x - x_i[2]) / (x_i[0] - x_i[2]));
-        let term_2 = y_i[1]
-            * ((new_x - x_i[0]) / (x_i[1] - x_i[0]))
-            * ((new_x - x_i[2]) / (x_i[1] - x_i[2]));
-        let term_3 = y_i[2]
-            * ((new_x - x_i[0]) / (x_i[2] - x_i[0]))
-            * ((new_x - x_i[1]) / (x_i[2] - x_i[1]));
-        term_1 + term_2 + term_3
-    }
-}
+// Give up on finding a polynomial after this many grid-sized steps
+const MAX_SAMPLES: i128 = 8;
 
-// This elf is very into ultra marathon running
-fn part_2_iterations(input: &str, iterations: usize) -> i64 {
+// Polynomial that maps the nr of steps to the nr of reachable positions, for steps that end at the edge of a grid copy
+fn growth_polynomial(input: &str) -> Polynomial {
     let grid: Vec<Vec<u8>> = input.lines().map(|line| line.as_bytes().to_vec()).collect();
     let max_x = grid.len() as i32;
     let max_y = grid[0].len() as i32;
@@ -150,27 +140,52 @@ fn part_2_iterations(input: &str, iterations: usize) -> i64 {
     let rocks = rock_positions(&grid);
 
     // The pattern of growth repeats after max_x steps (size of grid). The start is in the center.
-    // The number of positions that can be reached is a quadratic function
-    // -> Lagrange interpolation to map nr of iterations to nr of positions
+    // The number of positions that can be reached is a polynomial in the number of repeats (quadratic for real inputs)
+    // -> Sample until the differences confirm the degree, then Lagrange interpolation to map iterations to positions
     // -> Can be used to extrapolate to other iterations where the pattern repeats, which 26501365 luckily is.
-    let x_i: Vec<f64> = vec![
-        start.0 as f64,
-        start.0 as f64 + max_x as f64,
-        start.0 as f64 + 2.0 * max_x as f64,
-    ];
-    let y_i: Vec<f64> = x_i
-        .iter()
-        .map(|&iters| nr_of_possible_positions(&rocks, max_x, max_y, start, iters as usize) as f64)
-        .collect();
+    let mut points: Vec<(i128, i128)> = Vec::new();
+    for i in 0..MAX_SAMPLES {
+        let iters = start.0 as i128 + i * max_x as i128;
+        let positions = nr_of_possible_positions(&rocks, max_x, max_y, start, iters as usize);
+        points.push((iters, positions as i128));
+
+        let sequence: Vec<i128> = points.iter().map(|&(_, positions)| positions).collect();
+        if let Some(degree) = poly::degree(&sequence) {
+            return lagrange(&points[..=degree]);
+        }
+    }
+    panic!("Reachable positions do not grow polynomially within {MAX_SAMPLES} samples");
+}
 
-    let polynomial_fn = second_order_lagrange_polynomial(x_i, y_i);
-    polynomial_fn(iterations as f64) as i64
+// This elf is very into ultra marathon running
+fn part_2_iterations(input: &str, iterations: usize) -> i64 {
+    growth_polynomial(input).eval_integer(iterations as i128) as i64
 }
 
 pub fn part_2(input: &str) -> i64 {
     part_2_iterations(input, 26501365)
 }
 
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let polynomial = growth_polynomial(input);
+    let terms: Vec<String> = polynomial
+        .coefficients()
+        .iter()
+        .enumerate()
+        .map(|(power, coefficient)| format!("({coefficient}) * n^{power}"))
+        .collect();
+    visualizer.text(
+        "growth",
+        &format!(
+            "Reachable positions after n steps, degree {}:\n{}\n",
+            polynomial
+                .degree()
+                .map_or("none".to_string(), |d| d.to_string()),
+            terms.join(" + ")
+        ),
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,23 +215,20 @@ mod tests {
 
     #[test]
     fn test_lagrange_fn() {
-        let polynomial = second_order_lagrange_polynomial(vec![0.0, 1.0, 2.0], vec![0.0, 1.0, 4.0]);
-        assert_eq!(polynomial(0.0), 0.0);
-        assert_eq!(polynomial(1.0), 1.0);
-        assert_eq!(polynomial(2.0), 4.0);
-        assert_eq!(polynomial(3.0), 9.0);
-        assert_eq!(polynomial(4.0), 16.0);
-        assert_eq!(polynomial(10.0), 100.0);
-        assert_eq!(polynomial(20.0), 400.0);
+        let polynomial = lagrange(&[(0, 0), (1, 1), (2, 4)]);
+        assert_eq!(polynomial.eval_integer(0), 0);
+        assert_eq!(polynomial.eval_integer(1), 1);
+        assert_eq!(polynomial.eval_integer(2), 4);
+        assert_eq!(polynomial.eval_integer(3), 9);
+        assert_eq!(polynomial.eval_integer(4), 16);
+        assert_eq!(polynomial.eval_integer(10), 100);
+        assert_eq!(polynomial.eval_integer(20), 400);
     }
 
     #[test]
     fn test_lagrange_fn_2() {
-        let polynomial = second_order_lagrange_polynomial(
-            vec![65.0, 196.0, 327.0],
-            vec![3703.0, 32957.0, 91379.0],
-        );
-        assert_eq!(polynomial(26501365.0), 596857397104703.0);
+        let polynomial = lagrange(&[(65, 3703), (196, 32957), (327, 91379)]);
+        assert_eq!(polynomial.eval_integer(26501365), 596857397104703);
     }
 
     #[test]
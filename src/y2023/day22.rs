@@ -1,3 +1,6 @@
+use crate::util::render3d::{Scene, View};
+use crate::util::visualize::Visualizer;
+use image::{Rgb, RgbImage};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::f32::consts::PI;
 use std::usize;
@@ -111,7 +114,6 @@ impl Brick {
     }
 }
 
-#[allow(dead_code)]
 fn color_palette() -> Vec<(u8, u8, u8)> {
     vec![
         (255, 0, 0),   // Red
@@ -236,6 +238,50 @@ fn render(brick_points: &HashMap<Pos3D, BrickID>, connections: &HashMap<Pos3D, P
     });
 }
 
+// Same picture as `render`, but drawn in software to an image so it works without a window.
+// Bricks are drawn smaller than they are, so the support connections show in the gaps between them.
+// With `highlight`, only those bricks get their color and the others are gray.
+fn render_to_image(
+    brick_points: &HashMap<Pos3D, BrickID>,
+    connections: Option<&HashMap<Pos3D, Pos3D>>,
+    highlight: Option<&HashSet<BrickID>>,
+) -> RgbImage {
+    let mut bounds: HashMap<BrickID, (Pos3D, Pos3D)> = HashMap::new();
+    for (&p, &brick_id) in brick_points {
+        let (min, max) = bounds.entry(brick_id).or_insert((p, p));
+        *min = Pos3D::from(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+        *max = Pos3D::from(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+    }
+
+    let colors = color_palette();
+    let mut scene = Scene::new(Rgb([204, 204, 204]));
+    for (brick_id, (min, max)) in bounds {
+        let (r, g, b) = match highlight {
+            Some(highlight) if !highlight.contains(&brick_id) => (120, 120, 120),
+            _ => colors[brick_id % colors.len()],
+        };
+        scene.add_box(
+            [min.x as f64 - 0.3, min.y as f64 - 0.3, min.z as f64 - 0.3],
+            [max.x as f64 + 0.3, max.y as f64 + 0.3, max.z as f64 + 0.3],
+            Rgb([r, g, b]),
+        );
+    }
+    for (from, to) in connections.into_iter().flatten() {
+        scene.add_segment(
+            [from.x as f64, from.y as f64, from.z as f64],
+            [to.x as f64, to.y as f64, to.z as f64],
+            Rgb([0, 0, 0]),
+        );
+    }
+
+    // Inputs are tall towers, zoom out to keep the image a reasonable size
+    let max_z = brick_points.keys().map(|p| p.z).max().unwrap_or(1);
+    scene.render(&View {
+        pixels_per_unit: (1200.0 / max_z as f64).clamp(3.0, 40.0),
+        ..Default::default()
+    })
+}
+
 // Drop bricks down the z axis as far as possible
 fn drop_bricks(mut bricks: Vec<Brick>) -> Vec<Brick> {
     // sort bricks by height. Each brick will be dropped as low as possible
@@ -354,20 +400,24 @@ pub fn part_1(input: &str) -> usize {
     let (parents_to_children, children_to_parents) =
         bidirectional_support_graph(connections, brick_points);
 
-    let result = children_to_parents
-        .values()
-        .filter(|parents| {
+    disintegrable_bricks(bricks.len(), &parents_to_children, &children_to_parents).len()
+}
+
+fn disintegrable_bricks(
+    nr_of_bricks: usize,
+    parents_to_children: &HashMap<BrickID, HashSet<BrickID>>,
+    children_to_parents: &HashMap<BrickID, HashSet<BrickID>>,
+) -> HashSet<BrickID> {
+    (0..nr_of_bricks)
+        .filter(|brick_id| match children_to_parents.get(brick_id) {
             // each child can be disintegrated if its parent has more than 1 children
-            parents
+            Some(parents) => parents
                 .iter()
-                .all(|p| parents_to_children.get(p).unwrap().len() > 1)
+                .all(|p| parents_to_children.get(p).unwrap().len() > 1),
+            // orphans can always be disintegrated
+            None => true,
         })
-        .count();
-    let orphans = bricks.len() - children_to_parents.len();
-
-    // render(&brick_points, &connections);
-
-    result + orphans
+        .collect()
 }
 
 // For each brick, determine how many other bricks would fall if that brick were disintegrated.
@@ -406,6 +456,34 @@ pub fn part_2(input: &str) -> usize {
         .sum()
 }
 
+// Snapshots of the bricks before and after dropping, the connections between them,
+// and which bricks can be disintegrated
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let bricks: Vec<Brick> = input.lines().map(Brick::from_str).collect();
+    visualizer.image(
+        "begin",
+        &render_to_image(&brick_points(&bricks), None, None),
+    );
+
+    let bricks = drop_bricks(bricks);
+    let brick_points = brick_points(&bricks);
+    let connections = point_support_graph(&brick_points);
+    visualizer.image("dropped", &render_to_image(&brick_points, None, None));
+    visualizer.image(
+        "dropped_connections",
+        &render_to_image(&brick_points, Some(&connections), None),
+    );
+
+    let (parents_to_children, children_to_parents) =
+        bidirectional_support_graph(connections.clone(), brick_points.clone());
+    let disintegrable =
+        disintegrable_bricks(bricks.len(), &parents_to_children, &children_to_parents);
+    visualizer.image(
+        "disintegrable",
+        &render_to_image(&brick_points, Some(&connections), Some(&disintegrable)),
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -424,6 +502,18 @@ mod tests {
         assert_eq!(part_1(EXAMPLE_INPUT_1), 5);
     }
 
+    #[test]
+    fn test_render_to_image() {
+        let bricks = drop_bricks(EXAMPLE_INPUT_1.lines().map(Brick::from_str).collect());
+        let brick_points = brick_points(&bricks);
+        let img = render_to_image(&brick_points, None, Some(&HashSet::new()));
+        assert_eq!(*img.get_pixel(0, 0), Rgb([204, 204, 204]));
+        // Nothing is highlighted, so every brick is gray
+        assert!(img
+            .pixels()
+            .all(|p| p.0[0] == p.0[1] && p.0[1] == p.0[2] || p.0 == [0, 0, 0]));
+    }
+
     #[test]
     fn test_part_1() {
         assert_eq!(part_1(&load_input("inputs/2023/day_22")), 499);
@@ -1,3 +1,6 @@
+use crate::util::graph::Graph;
+use crate::util::layout::{force_directed, graph_to_svg, GraphStyle};
+use crate::util::visualize::Visualizer;
 use rand::Rng;
 use std::collections::HashSet;
 use std::str::Lines;
@@ -17,21 +20,6 @@ fn parse_edges(input: &str) -> Vec<(String, String)> {
         .collect()
 }
 
-// // Write dot file -> visualize with `dot -Kneato -Tpng -o output.png input.dot`
-// fn write_to_dot(edges: &Vec<(String, String)>, file_suffix: &str) -> std::io::Result<()> {
-//     let mut dot_content = String::new();
-
-//     dot_content.push_str("graph {\n");
-//     edges.iter().for_each(|(from, to)| {
-//         dot_content.push_str(&format!("  {} -- {};\n", from, to));
-//     });
-//     dot_content.push_str("}\n");
-
-//     File::create(format!("outputs/day_25{}.dot", file_suffix))?
-//         .write_all(dot_content.as_bytes())?;
-//     Ok(())
-// }
-
 // Replace random edge `from` -> `to` by contracted edge `fromto` - https://en.wikipedia.org/wiki/Karger's_algorithm
 fn contract_edge(nodes: &mut HashSet<String>, edges: &mut Vec<(String, String)>, edge_id: usize) {
     let (from, to) = edges.swap_remove(edge_id);
@@ -105,6 +93,46 @@ pub fn part_2(input: &str) -> usize {
     0
 }
 
+// Components that remain after removing the minimum cut get their own color, with the cut edges highlighted
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let edges = parse_edges(input);
+    let mut graph = Graph::undirected();
+    for (from, to) in &edges {
+        graph.add_edge(from, to);
+    }
+    let (_, side) = graph.min_cut().expect("Graph has less than two components");
+    let side: HashSet<&str> = side.into_iter().map(|id| graph.name(id)).collect();
+
+    // Same nodes in the same order, so node ids match between both graphs
+    let mut remaining = Graph::undirected();
+    (0..graph.len()).for_each(|id| {
+        remaining.add_node(graph.name(id));
+    });
+    edges
+        .iter()
+        .filter(|(from, to)| side.contains(from.as_str()) == side.contains(to.as_str()))
+        .for_each(|(from, to)| remaining.add_edge(from, to));
+    let mut groups = vec![0; graph.len()];
+    for (group, component) in remaining.connected_components().into_iter().enumerate() {
+        component.into_iter().for_each(|id| groups[id] = group);
+    }
+
+    let highlighted_edges = graph
+        .edges()
+        .filter(|&(from, to)| groups[from] != groups[to])
+        .collect();
+    let style = GraphStyle {
+        labels: graph.len() <= 50,
+        groups,
+        highlighted_edges,
+    };
+    let positions = force_directed(&graph, 300, 25);
+    visualizer.svg(
+        "components",
+        &graph_to_svg(&graph, &positions, &style, 1000.0),
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,6 @@
+use crate::util::bits::VisitedGrid;
+use crate::util::gif::{GifRecorder, Palette};
+use crate::util::visualize::Visualizer;
 use std::collections::HashSet;
 use std::usize;
 
@@ -136,32 +139,71 @@ fn parse_input(input: &str) -> (Grid, Position) {
     (Grid::from(obstacles, max_x, max_y), position)
 }
 
-// fn print_grid(grid: &Grid, visited: &HashSet<Position>, position: Position, direction: Direction) {
-//     for x in 0..grid.max_x {
-//         for y in 0..grid.max_y {
-//             if x == position.0 && y == position.1 {
-//                 print!("{}", direction)
-//             } else if grid.obstacles.contains(&(x, y)) {
-//                 print!("#");
-//             } else if visited.contains(&(x, y)) {
-//                 print!("█");
-//             } else {
-//                 print!(".")
-//             }
-//         }
-//         println!("");
-//     }
-//     println!("\n");
-// }
+fn grid_to_string(
+    grid: &Grid,
+    visited: &VisitedGrid,
+    guard: Option<(Position, Direction)>,
+) -> String {
+    let mut result = String::new();
+    for x in 0..grid.max_x {
+        for y in 0..grid.max_y {
+            if let Some((_, direction)) = guard.filter(|(position, _)| *position == (x, y)) {
+                result.push_str(&direction.to_string());
+            } else if grid.obstacles_horizontal[x].contains(&y) {
+                result.push('#');
+            } else if visited.contains((x, y)) {
+                result.push('X');
+            } else {
+                result.push('.');
+            }
+        }
+        result.push('\n');
+    }
+    result
+}
 
-fn walk_path(mut position: Position, mut direction: Direction, grid: &Grid) -> HashSet<Position> {
-    let mut visited = HashSet::new();
+// Positions from `from` up to and including `to`, in walking order
+fn positions_between(from: Position, to: Position) -> Vec<Position> {
+    let range = |a: usize, b: usize| -> Vec<usize> {
+        if a <= b {
+            (a..=b).collect()
+        } else {
+            (b..=a).rev().collect()
+        }
+    };
+    if from.0 == to.0 {
+        range(from.1, to.1)
+            .into_iter()
+            .map(|y| (from.0, y))
+            .collect()
+    } else {
+        range(from.0, to.0)
+            .into_iter()
+            .map(|x| (x, from.1))
+            .collect()
+    }
+}
+
+fn walk_path(
+    mut position: Position,
+    mut direction: Direction,
+    grid: &Grid,
+    recorder: &mut Option<GifRecorder>,
+) -> VisitedGrid {
+    let mut visited = VisitedGrid::new(grid.max_x, grid.max_y);
     loop {
         let next_position_result = grid.next_position(position, direction);
         let next_position = match next_position_result {
             Ok(p) => p,
             Err(p) => p,
         };
+        if let Some(recorder) = recorder {
+            // Walk step by step for the animation
+            for step in positions_between(position, next_position) {
+                visited.insert(step);
+                recorder.record_with(|| grid_to_string(grid, &visited, Some((step, direction))));
+            }
+        }
         // Insert all positions between current and next
         mark_positions_as_visited(position, next_position, direction, &mut visited);
 
@@ -179,7 +221,7 @@ fn mark_positions_as_visited(
     from: (usize, usize),
     to: (usize, usize),
     direction: Direction,
-    visited: &mut HashSet<(usize, usize)>,
+    visited: &mut VisitedGrid,
 ) {
     match direction {
         Direction::North | Direction::South => {
@@ -193,13 +235,12 @@ fn mark_positions_as_visited(
             }
         }
     }
-    // print_grid(grid, &visited, next_position, direction);
 }
 
 // Predict the path of the guard. How many distinct positions will the guard visit before leaving the mapped area?
 pub fn part_1(input: &str) -> usize {
     let (grid, start_position) = parse_input(input);
-    walk_path(start_position, Direction::North, &grid).len()
+    walk_path(start_position, Direction::North, &grid, &mut None).len()
 }
 
 // Check if guard loops starting from the given position and direction
@@ -221,11 +262,11 @@ fn guard_loops(grid: &Grid, mut position: Position, mut direction: Direction) ->
 // In how many positions can you place an obstacle to get the guard stuck in a loop?
 pub fn part_2(input: &str) -> usize {
     let (grid, start_position) = parse_input(input);
-    let visited: HashSet<Position> = walk_path(start_position, Direction::North, &grid);
+    let visited = walk_path(start_position, Direction::North, &grid, &mut None);
 
     // Instead of trying every possible position (16k), try only the path actually walked
     visited
-        .into_iter()
+        .iter()
         .filter(|position| {
             let mut new_grid = grid.clone();
             new_grid.insert_obstacle(*position);
@@ -234,6 +275,33 @@ pub fn part_2(input: &str) -> usize {
         .count()
 }
 
+// Animation of the guard walking its patrol route
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let (grid, start_position) = parse_input(input);
+    let guard = [255, 60, 60];
+    let palette = Palette::new(&[
+        ('#', [110, 110, 110]),
+        ('X', [60, 120, 200]),
+        ('^', guard),
+        ('>', guard),
+        ('V', guard),
+        ('<', guard),
+    ])
+    .background([20, 20, 30]);
+    // The guard takes about 5000 steps on the real input
+    let mut recorder = Some(
+        GifRecorder::new(palette)
+            .cell_size(4)
+            .delay_ms(20)
+            .every(10),
+    );
+    let visited = walk_path(start_position, Direction::North, &grid, &mut recorder);
+    let mut recorder = recorder.unwrap();
+    // The guard left the map at the end
+    recorder.record_final(&grid_to_string(&grid, &visited, None));
+    visualizer.gif("patrol", &recorder);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,5 @@
+use rand::rngs::StdRng;
+use rand::Rng;
 use std::usize;
 
 // Expand compact disk map representation into blocks view of file blocks and free space blocks.
@@ -146,6 +148,23 @@ pub fn part_2(input: &str) -> usize {
         .sum()
 }
 
+// Random disk map with `size` files, each followed by free space except the last one
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size.max(1))
+        .flat_map(|i| {
+            let file = rng.gen_range(1..=9);
+            let free = if i + 1 < size {
+                Some(rng.gen_range(0..=9))
+            } else {
+                None
+            };
+            [Some(file), free]
+        })
+        .flatten()
+        .map(|digit| char::from_digit(digit, 10).unwrap())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,5 @@
-use std::collections::{HashMap, HashSet};
+use crate::util::bits::VisitedGrid;
+use std::collections::HashMap;
 use std::usize;
 
 type Position = (i32, i32);
@@ -40,21 +41,27 @@ fn parse_input(input: &str) -> (HeightMap, Vec<Position>) {
 }
 
 // Score is number of reachable nines from the starting position
-fn trailhead_score(topographic_map: &HeightMap, start_pos: Position) -> usize {
-    let mut seen: HashSet<Position> = HashSet::new();
-    let mut nines: HashSet<Position> = HashSet::new();
+fn trailhead_score(
+    topographic_map: &HeightMap,
+    (rows, columns): (usize, usize),
+    start_pos: Position,
+) -> usize {
+    let mut seen = VisitedGrid::new(rows, columns);
+    let mut nines = VisitedGrid::new(rows, columns);
     let mut frontier = Vec::from([start_pos]);
 
+    // Every position in the frontier is on the map, so its coordinates are not negative
+    let index = |(x, y): Position| (x as usize, y as usize);
     while let Some(current) = frontier.pop() {
-        seen.insert(current);
+        seen.insert(index(current));
         let height = *topographic_map.get(&current).unwrap();
         if height == 9 {
-            nines.insert(current);
+            nines.insert(index(current));
             continue;
         }
         neighbors(current, height, &topographic_map)
             .into_iter()
-            .filter(|n| !seen.contains(n))
+            .filter(|&n| !seen.contains(index(n)))
             .for_each(|n| frontier.push(n));
     }
 
@@ -82,9 +89,13 @@ fn trailhead_rating(topographic_map: &HeightMap, start_pos: Position) -> usize {
 
 pub fn part_1(input: &str) -> usize {
     let (topographic_map, start_positions) = parse_input(input);
+    let size = (
+        input.lines().count(),
+        input.lines().map(str::len).max().unwrap_or(0),
+    );
     start_positions
         .into_iter()
-        .map(|pos| trailhead_score(&topographic_map, pos))
+        .map(|pos| trailhead_score(&topographic_map, size, pos))
         .sum()
 }
 
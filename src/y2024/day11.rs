@@ -1,3 +1,4 @@
+use crate::util::memo::Memo;
 use std::collections::HashMap;
 use std::usize;
 
@@ -50,42 +51,33 @@ fn blink(stone: u64) -> (u64, Option<u64>) {
 fn blink_stone_n_times_recursive(
     stone: u64,
     n: usize,
-    seen: &mut HashMap<(u64, usize), usize>,
+    memo: &mut Memo<(u64, usize), usize>,
 ) -> usize {
-    fn recurse(seen: &mut HashMap<(u64, usize), usize>, stone: u64, n: usize) -> usize {
-        if let Some(count) = seen.get(&(stone, n)) {
-            *count
+    memo.get_or_compute((stone, n), |memo| {
+        if n == 0 {
+            1
         } else {
-            blink_stone_n_times_recursive(stone, n, seen)
-        }
-    }
-
-    if n == 0 {
-        seen.insert((stone, 0), 1);
-        1
-    } else {
-        let (left, right) = blink(stone);
+            let (left, right) = blink(stone);
 
-        let left_count = recurse(seen, left, n - 1);
-        let right_count = if let Some(right) = right {
-            recurse(seen, right, n - 1)
-        } else {
-            0
-        };
+            let left_count = blink_stone_n_times_recursive(left, n - 1, memo);
+            let right_count = if let Some(right) = right {
+                blink_stone_n_times_recursive(right, n - 1, memo)
+            } else {
+                0
+            };
 
-        let result = left_count + right_count;
-        seen.insert((stone, n), result);
-        result
-    }
+            left_count + right_count
+        }
+    })
 }
 
 #[allow(dead_code)]
 fn blink_stones_n_times_recursive(stones: Stones, n: usize) -> usize {
-    let mut seen: HashMap<(u64, usize), usize> = HashMap::new();
+    let mut memo = Memo::new();
     let mut result = 0;
 
     for &stone in &stones {
-        result += blink_stone_n_times_recursive(stone, n, &mut seen);
+        result += blink_stone_n_times_recursive(stone, n, &mut memo);
     }
 
     result
@@ -131,7 +123,9 @@ pub fn part_2(input: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::differential::{assert_agree, shrink_number, shrink_vec};
     use crate::util::util::load_input;
+    use rand::Rng;
 
     const EXAMPLE_INPUT: &str = "125 17";
 
@@ -208,4 +202,31 @@ mod tests {
     fn test_part_2() {
         assert_eq!(part_2(&load_input("inputs/2024/day_11")), 225404711855335);
     }
+
+    #[test]
+    fn test_blink_recursive_differential() {
+        assert_agree(
+            100,
+            |rng| {
+                let stones: Stones = (0..rng.gen_range(1..5))
+                    .map(|_| rng.gen_range(0..100_000))
+                    .collect();
+                (stones, rng.gen_range(0..30))
+            },
+            |(stones, n): &(Stones, usize)| {
+                let fewer_stones = shrink_vec(stones, |&s| {
+                    shrink_number(s as usize)
+                        .into_iter()
+                        .map(|s| s as u64)
+                        .collect()
+                })
+                .into_iter()
+                .map(|s| (s, *n));
+                let fewer_blinks = shrink_number(*n).into_iter().map(|n| (stones.clone(), n));
+                fewer_stones.chain(fewer_blinks).collect()
+            },
+            |(stones, n)| blink_stones_n_times_recursive(stones.clone(), *n),
+            |(stones, n)| blink_stones_n_times(stones.clone(), *n),
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use crate::util::dsu::DisjointSet;
 
 type Position = (i32, i32);
 type Garden = Vec<Vec<char>>;
@@ -10,6 +10,33 @@ fn parse_garden(input: &str) -> (Garden, i32, i32) {
     (garden, max_x, max_y)
 }
 
+// Group the plots into regions by joining every plot with the plots of the same plant to its right and below it
+fn regions(garden: &Garden) -> Vec<Vec<Position>> {
+    let (rows, columns) = (garden.len(), garden[0].len());
+    let mut plots = DisjointSet::new(rows * columns);
+    for (x, row) in garden.iter().enumerate() {
+        for (y, &plant) in row.iter().enumerate() {
+            let i = x * columns + y;
+            if y + 1 < columns && row[y + 1] == plant {
+                plots.union(i, i + 1);
+            }
+            if x + 1 < rows && garden[x + 1][y] == plant {
+                plots.union(i, i + columns);
+            }
+        }
+    }
+    plots
+        .components()
+        .into_iter()
+        .map(|region| {
+            region
+                .into_iter()
+                .map(|i| ((i / columns) as i32, (i % columns) as i32))
+                .collect()
+        })
+        .collect()
+}
+
 // Get the neighboring positions along with the plant value on those positions if they exist
 fn neighbors(
     pos: Position,
@@ -59,34 +86,19 @@ fn perimeter_of_plant(
 // What is the total price of fencing all regions on your map?
 pub fn part_1(input: &str) -> usize {
     let (garden, max_x, max_y) = parse_garden(input);
-    let mut seen: HashSet<Position> = HashSet::new();
-    let mut result = 0;
-
-    for (x, row) in garden.iter().enumerate() {
-        for (y, &plant) in row.iter().enumerate() {
-            let position = (x as i32, y as i32);
-
-            if seen.insert(position) {
-                // Follow the neighbors that contain the same plants to detect the whole region
-                let mut frontier = Vec::from([position]);
-                let (mut area, mut perimeter) = (0, 0);
 
-                while let Some(current_pos) = frontier.pop() {
-                    area += 1;
-                    perimeter += perimeter_of_plant(plant, current_pos, &garden, max_x, max_y);
-
-                    for (next_pos, next_plant) in neighbors(current_pos, &garden, max_x, max_y) {
-                        if next_plant.unwrap_or_default() == plant && seen.insert(next_pos) {
-                            frontier.push(next_pos);
-                        }
-                    }
-                }
-                result += area * perimeter
-            }
-        }
-    }
-
-    result
+    regions(&garden)
+        .iter()
+        .map(|region| {
+            let (x, y) = region[0];
+            let plant = garden[x as usize][y as usize];
+            let perimeter: usize = region
+                .iter()
+                .map(|&position| perimeter_of_plant(plant, position, &garden, max_x, max_y))
+                .sum();
+            region.len() * perimeter
+        })
+        .sum()
 }
 
 #[derive(Debug)]
@@ -177,7 +189,7 @@ fn contract_edges(edges: &mut Vec<Edge>) {
 }
 
 // Count the size and total nr of sides of the given region.
-// To get total nr of sides: build a set of edges while visiting the region and contract edges.
+// To get total nr of sides: build a set of edges around the plants of the region and contract edges.
 // Build edges by placing them around each plant in different directions. Possible situations:
 //   * Plant has no same neighbors: 4 edges around the plant
 //   * Plant has 1 same neighbor: 3 edges around and none between the same neighbor
@@ -187,55 +199,39 @@ fn contract_edges(edges: &mut Vec<Edge>) {
 //   At most 4 edges. Edges can only go on sides that touch other neighbors.
 //   In other words, always an edge between a plant and a neighboring other plant.
 fn area_and_sides_of_region(
-    start_position: Position,
-    plant: char,
+    region: &[Position],
     garden: &Garden,
-    seen: &mut HashSet<Position>,
     max_x: i32,
     max_y: i32,
 ) -> (usize, usize) {
-    let mut area = 0;
+    let (x, y) = region[0];
+    let plant = garden[x as usize][y as usize];
     let mut edges: Vec<Edge> = Vec::new();
 
-    let mut frontier = Vec::from([start_position]);
-    while let Some(current_pos) = frontier.pop() {
-        if seen.insert(current_pos) {
-            area += 1;
-
-            for (next_pos, next_plant) in neighbors(current_pos, garden, max_x, max_y) {
-                if next_plant.unwrap_or_default() == plant {
-                    // Visit same neighbor
-                    frontier.push(next_pos);
-                } else {
-                    // Create edge between differing neighbors
-                    edges.push(Edge::from_neighbors(current_pos, next_pos));
-                }
+    for &current_pos in region {
+        for (next_pos, next_plant) in neighbors(current_pos, garden, max_x, max_y) {
+            // Create edge between differing neighbors
+            if next_plant != Some(plant) {
+                edges.push(Edge::from_neighbors(current_pos, next_pos));
             }
         }
     }
     contract_edges(&mut edges);
 
-    (area, edges.len())
+    (region.len(), edges.len())
 }
 
 // Total price if counting sides of regions instead of perimeter
 pub fn part_2(input: &str) -> usize {
     let (garden, max_x, max_y) = parse_garden(input);
-    let mut seen: HashSet<Position> = HashSet::new();
-    let mut result = 0;
-
-    for (x, row) in garden.iter().enumerate() {
-        for (y, plant) in row.iter().enumerate() {
-            let position = (x as i32, y as i32);
-            if !seen.contains(&position) {
-                let (area, edges) =
-                    area_and_sides_of_region(position, *plant, &garden, &mut seen, max_x, max_y);
-                result += area * edges;
-            }
-        }
-    }
 
-    result
+    regions(&garden)
+        .iter()
+        .map(|region| {
+            let (area, edges) = area_and_sides_of_region(region, &garden, max_x, max_y);
+            area * edges
+        })
+        .sum()
 }
 
 #[cfg(test)]
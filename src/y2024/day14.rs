@@ -1,7 +1,11 @@
+use crate::util::frames::{self, FrameScorer, FrameStats};
+use crate::util::gif::{render_grid, GifRecorder, Palette};
+use crate::util::parse::Pattern;
+use crate::util::visualize::Visualizer;
+use rand::rngs::StdRng;
+use rand::Rng;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::Write;
-use std::str::{FromStr, Lines};
+use std::str::Lines;
 use std::usize;
 
 const MAX_X: i32 = 103;
@@ -53,47 +57,16 @@ impl Robot {
     }
 }
 
-impl std::str::FromStr for Robot {
-    type Err = String;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let mut parts = input.split_whitespace();
-
-        let p = parts
-            .next()
-            .unwrap_or("Missing first part")
-            .strip_prefix("p=")
-            .ok_or("Missing 'p=' prefix")?
-            .split(',')
-            .map(str::parse)
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|_| "Invalid position coordinates")?;
-
-        let v = parts
-            .next()
-            .unwrap_or("Missing second part")
-            .strip_prefix("v=")
-            .ok_or("Missing 'v=' prefix")?
-            .split(',')
-            .map(str::parse)
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|_| "Invalid velocity coordinates")?;
-
-        if p.len() == 2 && v.len() == 2 {
-            Ok(Robot {
-                p: (p[1], p[0]),
-                v: (v[1], v[0]),
-            })
-        } else {
-            Err("Incorrect number of coordinates".to_string())
-        }
-    }
-}
-
 fn parse_robots(input: &str) -> Vec<Robot> {
-    input
-        .lines()
-        .map(|line| Robot::from_str(line).expect("Could not parse robot"))
+    Pattern::new("p={},{} v={},{}")
+        .parse_lines(input)
+        .expect("Could not parse robots")
+        .into_iter()
+        // Input is given as (column, row)
+        .map(|(py, px, vy, vx)| Robot {
+            p: (px, py),
+            v: (vx, vy),
+        })
         .collect()
 }
 
@@ -117,61 +90,118 @@ pub fn part_1(input: &str) -> usize {
     safety_score(&robots, MAX_X, MAX_Y)
 }
 
-#[allow(dead_code)]
-fn display_grid(robots: &[Robot], file: &mut File, max_x: usize, max_y: usize) {
+fn grid_to_string(robots: &[Robot], max_x: usize, max_y: usize) -> String {
     let mut grid = vec![vec![0; max_y]; max_x];
 
     for robot in robots {
         grid[robot.p.0 as usize][robot.p.1 as usize] += 1;
     }
 
-    for row in grid.iter() {
-        for &cell in row.iter() {
-            if cell > 0 {
-                file.write_fmt(format_args!("{}", cell)).unwrap();
-            } else {
-                file.write(b".").unwrap();
-            }
-        }
-        file.write(b".\n").unwrap();
-    }
-    file.write(b"\n").unwrap();
+    grid.iter()
+        .map(|row| {
+            row.iter()
+                .map(|&cell| {
+                    if cell > 0 {
+                        cell.to_string()
+                    } else {
+                        ".".to_string()
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
-pub fn part_2(input: &str) -> i32 {
-    // let mut seen: HashMap<Vec<Robot>, i32> = HashMap::new();
-    let mut robots = parse_robots(input);
-    // Most of the safety scores are close to the score of part 1 since they have a uniform distribution of robots
-    // Robots that form a pattern should result in a significantly different safety score
-    // Sorting by safety score gives us the most interesting patterns to check out first
-    // let mut history: Vec<(i32, usize, Vec<Robot>)> = Vec::new();
-    let mut scores: Vec<(i32, usize)> = Vec::new();
+// Nr of ranked frames that are written as images by `visualize`
+const TOP_FRAMES: usize = 10;
 
+fn find_christmas_tree(input: &str, visualizer: &mut Visualizer) -> i32 {
+    let initial = parse_robots(input);
+    // Most frames have the robots spread uniformly, the tree is the frame that stands out most
+    // on the measures of `frames::rank`: clustered, with a large connected shape, and compressible
     // Robots loop every max_x * max_y steps
-    for i in 1..(MAX_X * MAX_Y) {
-        robots = robots.drain(..).map(|r| r.step(1, MAX_X, MAX_Y)).collect();
-        // if let Some(previous_iter) = seen.insert(robots.clone(), i) { println!("previous: {}", previous_iter); }
-        // history.push((i, safety_score(&robots, MAX_X, MAX_Y), robots.clone()));
-        scores.push((i, safety_score(&robots, MAX_X, MAX_Y)));
+    let mut scorer = FrameScorer::new(MAX_X as usize, MAX_Y as usize);
+    let mut points: Vec<(usize, usize)> = Vec::with_capacity(initial.len());
+    let stats: Vec<FrameStats> = (1..MAX_X * MAX_Y)
+        .map(|i| {
+            points.clear();
+            points.extend(
+                initial
+                    .iter()
+                    .map(|r| r.step(i, MAX_X, MAX_Y).p)
+                    .map(|(x, y)| (x as usize, y as usize)),
+            );
+            scorer.stats(i as usize, &points)
+        })
+        .collect();
+    let ranked = frames::rank(&stats);
+    let tree = ranked[0].1.step as i32;
+
+    if visualizer.is_enabled() {
+        visualizer.text(
+            "frame_scores",
+            &frames::table(&ranked[..TOP_FRAMES.min(ranked.len())]),
+        );
+
+        let robot = [80, 200, 80];
+        let palette = Palette::new(&[('1', robot), ('2', robot), ('3', robot), ('4', robot)])
+            .background([20, 20, 30]);
+        for (rank, (_, frame)) in ranked.iter().take(TOP_FRAMES).enumerate() {
+            let robots: Vec<Robot> = initial
+                .iter()
+                .map(|r| r.step(frame.step as i32, MAX_X, MAX_Y))
+                .collect();
+            let grid = grid_to_string(&robots, MAX_X as usize, MAX_Y as usize);
+            visualizer.image(
+                &format!("frame_{:02}_step_{}", rank + 1, frame.step),
+                &render_grid(&grid, &palette, 4),
+            );
+        }
+
+        // Robots coming together into the tree
+        let mut recorder = GifRecorder::new(palette).cell_size(4).delay_ms(150);
+        for i in (tree - 30).max(0)..=tree {
+            let robots: Vec<Robot> = initial.iter().map(|r| r.step(i, MAX_X, MAX_Y)).collect();
+            recorder.record_with(|| grid_to_string(&robots, MAX_X as usize, MAX_Y as usize));
+        }
+        visualizer.gif("christmas_tree", &recorder);
     }
 
-    // history.sort_by(|a, b| a.1.cmp(&b.1));
-    // let mut file = File::create("outputs/2024/day14_part2.txt").unwrap();
-    // for (iteration, score, robots) in &history[0..20] {
-    //     file.write_fmt(format_args!("Iteration {}, score {}\n", iteration, score))
-    //         .unwrap();
-    //     display_grid(&robots, &mut file, MAX_X as usize, MAX_Y as usize);
-    // }
+    tree
+}
 
-    // The third lowest score contains the chrismas tree
-    scores.sort_by(|a, b| a.1.cmp(&b.1));
-    scores[2].0
+pub fn part_2(input: &str) -> i32 {
+    find_christmas_tree(input, &mut Visualizer::disabled())
+}
+
+// Writes the scores of the most interesting frames and images of them, the first one shows the christmas tree,
+// and an animation of the robots forming the tree
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    find_christmas_tree(input, visualizer);
+}
+
+// Random list of `size` robots on the 101 x 103 space
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "p={},{} v={},{}",
+                rng.gen_range(0..MAX_Y),
+                rng.gen_range(0..MAX_X),
+                rng.gen_range(-100..=100),
+                rng.gen_range(-100..=100)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::util::util::load_input;
+    use rand::SeedableRng;
 
     const EXAMPLE_INPUT: &str = "p=0,4 v=3,-3
 p=6,3 v=-1,-3
@@ -215,6 +245,30 @@ p=9,5 v=-3,-3";
         assert_eq!(safety_score(&robots, max_x, max_y), 12);
     }
 
+    #[test]
+    fn test_part_2_hidden_picture() {
+        // Half of the robots form a filled triangle at step 4321, the other half is noise
+        let mut rng = StdRng::seed_from_u64(14);
+        let step = 4321;
+        let triangle = (0..20).flat_map(|x| (30 - x..=30 + x).map(move |y| (40 + x, y)));
+        let noise: Vec<Position> = (0..400)
+            .map(|_| (rng.gen_range(0..MAX_X), rng.gen_range(0..MAX_Y)))
+            .collect();
+        let input: Vec<String> = triangle
+            .chain(noise)
+            .map(|(x, y)| {
+                let (vx, vy) = (rng.gen_range(-100..=100), rng.gen_range(-100..=100));
+                let start = Robot {
+                    p: (x, y),
+                    v: (vx, vy),
+                }
+                .step(-step, MAX_X, MAX_Y);
+                format!("p={},{} v={},{}", start.p.1, start.p.0, vy, vx)
+            })
+            .collect();
+        assert_eq!(part_2(&input.join("\n")), step);
+    }
+
     #[test]
     fn test_part_1() {
         assert_eq!(part_1(&load_input("inputs/2024/day_14")), 230172768);
@@ -1,3 +1,5 @@
+use crate::util::gif::{GifRecorder, Palette};
+use crate::util::visualize::Visualizer;
 use std::collections::HashMap;
 use std::usize;
 
@@ -67,34 +69,57 @@ fn parse_grid(input: &str) -> (Grid, Position) {
     (g, robot_pos)
 }
 
-fn display_grid(grid: &Grid, robot: &Position, d: &Direction) {
-    let max_x = *grid.keys().map(|(x, _)| x).max().unwrap();
-    let max_y = *grid.keys().map(|(_, y)| y).max().unwrap();
-
+fn grid_to_string(
+    grid: &Grid,
+    (max_x, max_y): Position,
+    robot: &Position,
+    d: &Direction,
+) -> String {
+    let mut result = String::new();
     for x in 0..=max_x {
         for y in 0..=max_y {
-            if let Some(tile) = grid.get(&(x, y)) {
-                let c = match tile {
-                    Tile::Box => "O",
-                    Tile::Wall => "#",
-                    Tile::LeftHalfBox => "[",
-                    Tile::RightHalfBox => "]",
-                };
-                print!("{}", c);
+            let c = if let Some(tile) = grid.get(&(x, y)) {
+                match tile {
+                    Tile::Box => 'O',
+                    Tile::Wall => '#',
+                    Tile::LeftHalfBox => '[',
+                    Tile::RightHalfBox => ']',
+                }
             } else if &(x, y) == robot {
                 match d {
-                    Direction::Up => print!("^"),
-                    Direction::Right => print!(">"),
-                    Direction::Down => print!("v"),
-                    Direction::Left => print!("<"),
+                    Direction::Up => '^',
+                    Direction::Right => '>',
+                    Direction::Down => 'v',
+                    Direction::Left => '<',
                 }
             } else {
-                print!(".");
-            }
+                '.'
+            };
+            result.push(c);
         }
-        println!();
+        result.push('\n');
     }
-    println!();
+    result
+}
+
+fn warehouse_recorder() -> GifRecorder {
+    let robot = [255, 60, 60];
+    let palette = Palette::new(&[
+        ('#', [110, 110, 110]),
+        ('O', [205, 150, 70]),
+        ('[', [205, 150, 70]),
+        (']', [175, 120, 50]),
+        ('^', robot),
+        ('>', robot),
+        ('v', robot),
+        ('<', robot),
+    ])
+    .background([20, 20, 30]);
+    // Inputs have 20000 moves, every 20th one makes for a watchable animation
+    GifRecorder::new(palette)
+        .cell_size(6)
+        .delay_ms(20)
+        .every(20)
 }
 
 // Part 1 moves all boxes in front of the robot until they block each other against a wall
@@ -235,18 +260,29 @@ fn move_robot(grid: &mut Grid, pos: Position, d: &Direction) -> Position {
     }
 }
 
-fn do_moves(moves: &str, mut robot: Position, grid: &mut HashMap<(usize, usize), Tile>) {
-    // println!("Begin:");
-    // display_grid(&grid, &robot, &Direction::from(moves.chars().nth(0).unwrap()));
+fn do_moves(
+    moves: &str,
+    mut robot: Position,
+    grid: &mut HashMap<(usize, usize), Tile>,
+    recorder: &mut Option<GifRecorder>,
+) {
+    // Walls surround the warehouse, so the corner opposite of (0, 0) stays the same for every frame
+    let max = grid
+        .keys()
+        .fold((0, 0), |max, &(x, y)| (max.0.max(x), max.1.max(y)));
+    let mut d = Direction::Up;
     for line in moves.lines() {
         for c in line.chars() {
-            let d = Direction::from(c);
+            d = Direction::from(c);
             robot = move_robot(grid, robot, &d);
-            // display_grid(grid, &robot, &d);
+            if let Some(recorder) = recorder {
+                recorder.record_with(|| grid_to_string(grid, max, &robot, &d));
+            }
         }
     }
-    // println!("\nEnd:");
-    // display_grid(&grid, &robot, &Direction::from(moves.trim_end().chars().last().unwrap()));
+    if let Some(recorder) = recorder {
+        recorder.record_final(&grid_to_string(grid, max, &robot, &d));
+    }
 }
 
 fn gps_coordinate(tile: &Tile, (x, y): Position) -> usize {
@@ -269,7 +305,7 @@ fn double_everything(grid: &str) -> String {
     new_grid
 }
 
-fn solution(input: &str, double_all_the_things: bool) -> usize {
+fn solution(input: &str, double_all_the_things: bool, visualizer: &mut Visualizer) -> usize {
     let (mut grid, moves) = input
         .split_once("\n\n")
         .map(|(s1, s2)| (s1.to_string(), s2.to_string()))
@@ -278,18 +314,33 @@ fn solution(input: &str, double_all_the_things: bool) -> usize {
         grid = double_everything(&grid);
     }
     let (mut grid, robot) = parse_grid(&grid);
-    do_moves(&moves, robot, &mut grid);
+    let mut recorder = visualizer.is_enabled().then(warehouse_recorder);
+    do_moves(&moves, robot, &mut grid, &mut recorder);
+    if let Some(recorder) = recorder {
+        let name = if double_all_the_things {
+            "part_2"
+        } else {
+            "part_1"
+        };
+        visualizer.gif(name, &recorder);
+    }
     grid.iter()
         .map(|(&position, tile)| gps_coordinate(tile, position))
         .sum()
 }
 
 pub fn part_1(input: &str) -> usize {
-    solution(input, false)
+    solution(input, false, &mut Visualizer::disabled())
 }
 
 pub fn part_2(input: &str) -> usize {
-    solution(input, true)
+    solution(input, true, &mut Visualizer::disabled())
+}
+
+// Animations of the robot pushing boxes around the warehouse for both parts
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    solution(input, false, visualizer);
+    solution(input, true, visualizer);
 }
 
 #[cfg(test)]
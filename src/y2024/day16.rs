@@ -1,3 +1,5 @@
+use crate::util::heatmap::Heatmap;
+use crate::util::visualize::Visualizer;
 use std::cmp::Reverse;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::str::Lines;
@@ -121,9 +123,8 @@ pub fn part_1(input: &str) -> usize {
     dijkstra(&grid, start_move, success, successors)
 }
 
-// Get all cells that lie on one of the possible best paths
-fn all_cells_on_a_best_path(grid: &Grid, start: Position, goal: Position) -> HashSet<Position> {
-    // For each position, keep the lowest cost to reach it and the preceding move(s) that lead to it
+// For each position, the lowest cost to reach it and the preceding move(s) that lead to it
+fn lowest_costs(grid: &Grid, start: Position) -> HashMap<Position, (usize, Vec<Move>)> {
     let mut best: HashMap<Position, (usize, Vec<Move>)> = HashMap::new();
     let start_move = Move {
         cost: 0,
@@ -162,6 +163,13 @@ fn all_cells_on_a_best_path(grid: &Grid, start: Position, goal: Position) -> Has
         }
     }
 
+    best
+}
+
+// Get all cells that lie on one of the possible best paths
+fn all_cells_on_a_best_path(grid: &Grid, start: Position, goal: Position) -> HashSet<Position> {
+    let best = lowest_costs(grid, start);
+
     // Follow the best predecessor(s) for each position starting from the end
     let mut output: HashSet<Position> = HashSet::from([start, goal]);
     let mut reverse_frontier = vec![goal];
@@ -203,6 +211,26 @@ pub fn part_2(input: &str) -> usize {
     cells.len()
 }
 
+// Lowest score to reach every tile, with the tiles of the best paths on top.
+// Turning costs 1000, so the colors jump at every corner of the maze.
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let (grid, start, goal) = parse_input(input);
+    let width = grid[0].len();
+    let index = |(x, y): Position| x as usize * width + y as usize;
+
+    let mut heatmap = Heatmap::new(width, grid.len());
+    heatmap.record(index(start), 0);
+    for (&position, &(cost, _)) in &lowest_costs(&grid, start) {
+        heatmap.record(index(position), cost);
+    }
+    let best_paths: Vec<usize> = all_cells_on_a_best_path(&grid, start, goal)
+        .into_iter()
+        .map(index)
+        .collect();
+    let cell_size = (1000 / width).clamp(2, 20) as u32;
+    visualizer.image("scores", &heatmap.render(&best_paths, cell_size));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,4 @@
-use std::usize;
+use crate::util::parse::{sections, uints};
 
 type Program = Vec<u32>;
 
@@ -23,30 +23,15 @@ enum Instruction {
 }
 
 fn parse_input(input: &str) -> (Registers, Program) {
-    let mut lines = input.lines();
-    let mut next_register = || {
-        lines
-            .next()
-            .expect("Could not read next line for register")
-            .split_once(": ")
-            .expect("Invalid register line")
-            .1
-            .parse()
-            .expect("Could not parse register")
-    };
+    let mut sections = sections(input);
+    let mut registers = uints(sections.next().expect("Missing registers").as_bytes());
+    let mut next_register = || registers.next().expect("Missing register value");
     let a = next_register();
     let b = next_register();
     let c = next_register();
 
-    let program = lines
-        .skip(1)
-        .next()
-        .expect("Could not read line for program")
-        .split_once(": ")
-        .expect("Invalid line for program")
-        .1
-        .chars()
-        .filter_map(|c| c.to_digit(10))
+    let program = uints(sections.next().expect("Missing program").as_bytes())
+        .map(|n| n as u32)
         .collect();
 
     (Registers { a, b, c }, program)
@@ -1,26 +1,17 @@
-use std::collections::HashMap;
+use crate::util::memo::Memo;
+use crate::util::visualize::Visualizer;
 use std::usize;
 
 // Is is possible to create the desired design using the available patterns?
 // Recursive approach: if the design can be broken down by the given patterns, then it is possible
-fn is_possible(design: &str, patterns: &Vec<&str>, cache: &mut HashMap<String, bool>) -> bool {
-    // base case, no need to split further
-    if let Some(result) = cache.get(design) {
-        return *result;
-    }
-
-    for pattern in patterns {
-        if design.starts_with(pattern) {
-            let next_design = &design[pattern.len()..];
-            if is_possible(next_design, patterns, cache) {
-                cache.insert(next_design.to_string(), true);
-                return true;
-            }
-        }
-    }
-
-    cache.insert(design.to_string(), false);
-    false
+fn is_possible(design: &str, patterns: &Vec<&str>, memo: &mut Memo<String, bool>) -> bool {
+    memo.get_or_compute(design.to_string(), |memo| {
+        patterns.iter().any(|pattern| {
+            design
+                .strip_prefix(pattern)
+                .is_some_and(|next_design| is_possible(next_design, patterns, memo))
+        })
+    })
 }
 
 // How many designs are possible?
@@ -30,11 +21,12 @@ pub fn part_1(input: &str) -> usize {
         .expect("Could not split input in 2 parts");
     let mut patterns: Vec<&str> = patterns.split(", ").collect();
     patterns.sort_by(|a, b| b.len().cmp(&a.len()));
-    let mut cache: HashMap<String, bool> = patterns.iter().map(|p| (p.to_string(), true)).collect();
+    // base case, no need to split further
+    let mut memo: Memo<String, bool> = patterns.iter().map(|p| (p.to_string(), true)).collect();
 
     designs
         .lines()
-        .filter(|design| is_possible(design, &patterns, &mut cache))
+        .filter(|design| is_possible(design, &patterns, &mut memo))
         .count()
 }
 
@@ -42,55 +34,57 @@ pub fn part_1(input: &str) -> usize {
 fn nr_of_possible_arrangements(
     design: String,
     patterns: &Vec<&str>,
-    cache: &mut HashMap<String, usize>,
+    memo: &mut Memo<String, usize>,
 ) -> usize {
-    // base case, no need to split further
-    if let Some(result) = cache.get(&design) {
-        return *result;
-    } else if design.len() == 1 {
-        if patterns.contains(&&design[..]) {
-            cache.insert(design, 1);
-            return 1;
-        } else {
-            cache.insert(design, 0);
-            return 0;
+    memo.get_or_compute(design.clone(), |memo| {
+        // base case, no need to split further
+        if design.len() == 1 {
+            return patterns.contains(&&design[..]) as usize;
         }
-    }
 
-    let mut nr_of_arrangements = 0;
-    for pattern in patterns {
-        if design.starts_with(pattern) {
-            if &design == pattern {
-                nr_of_arrangements += 1;
-            } else {
-                nr_of_arrangements += nr_of_possible_arrangements(
-                    design[pattern.len()..].to_string(),
-                    patterns,
-                    cache,
-                );
+        let mut nr_of_arrangements = 0;
+        for pattern in patterns {
+            if design.starts_with(pattern) {
+                if &design == pattern {
+                    nr_of_arrangements += 1;
+                } else {
+                    nr_of_arrangements += nr_of_possible_arrangements(
+                        design[pattern.len()..].to_string(),
+                        patterns,
+                        memo,
+                    );
+                }
             }
         }
-    }
-
-    cache.insert(design, nr_of_arrangements);
-    nr_of_arrangements
+        nr_of_arrangements
+    })
 }
 
-// What do you get if you add up the number of different ways you could make each design?
-pub fn part_2(input: &str) -> usize {
+fn total_arrangements(input: &str, memo: &mut Memo<String, usize>) -> usize {
     let (patterns, designs) = input
         .split_once("\n\n")
         .expect("Could not split input in 2 parts");
     let mut patterns: Vec<&str> = patterns.split(", ").collect();
     patterns.sort_by(|a, b| b.len().cmp(&a.len()));
-    let mut cache: HashMap<String, usize> = HashMap::new();
 
     designs
         .lines()
-        .map(|design| nr_of_possible_arrangements(design.to_string(), &patterns, &mut cache))
+        .map(|design| nr_of_possible_arrangements(design.to_string(), &patterns, memo))
         .sum()
 }
 
+// What do you get if you add up the number of different ways you could make each design?
+pub fn part_2(input: &str) -> usize {
+    total_arrangements(input, &mut Memo::new())
+}
+
+// How well the cache of part 2 works, every design shares its suffixes with the others
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let mut memo = Memo::new();
+    total_arrangements(input, &mut memo);
+    visualizer.text("memo", &format!("{}\n", memo));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,5 @@
+use rand::rngs::StdRng;
+use rand::Rng;
 use std::cmp::Reverse;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::hash::Hash;
@@ -147,6 +149,38 @@ pub fn part_2(input: &str) -> usize {
     nr_of_cheats(path, 20)
 }
 
+/// Racetrack that snakes back and forth through the grid, with a path of about `size` picoseconds.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let track_width = rng.gen_range(10..=40);
+    let nr_of_rows = size.div_ceil(track_width + 1).max(1);
+    let wall = "#".repeat(track_width + 2);
+
+    let mut lines = vec![wall.clone()];
+    for row in 0..nr_of_rows {
+        let mut track: Vec<char> = format!("#{}#", ".".repeat(track_width)).chars().collect();
+        // Odd rows run right to left
+        let (first, last) = if row % 2 == 0 {
+            (1, track_width)
+        } else {
+            (track_width, 1)
+        };
+        if row == 0 {
+            track[first] = 'S';
+        }
+        if row == nr_of_rows - 1 {
+            track[last] = 'E';
+            lines.push(track.into_iter().collect());
+        } else {
+            lines.push(track.into_iter().collect());
+            let mut connection: Vec<char> = wall.chars().collect();
+            connection[last] = '.';
+            lines.push(connection.into_iter().collect());
+        }
+    }
+    lines.push(wall);
+    lines.join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,12 +1,13 @@
+use crate::util::memo::Memo;
 use std::collections::HashMap;
 
 type Position = (i32, i32);
 type Path = Vec<char>;
 type Keypad = HashMap<Position, char>;
 // All shortest paths between two buttons
-type PathsCache = HashMap<(char, char), Vec<Path>>;
+type PathsCache = Memo<(char, char), Vec<Path>>;
 // Amounts of total button presses between two buttons on layers of keypads
-type CountsCache = HashMap<(char, char, usize), usize>;
+type CountsCache = Memo<(char, char, usize), usize>;
 
 // The numeric keypad has four rows of buttons: 789, 456, 123, and finally an empty gap followed by 0A.
 const NUMERIC_KEYPAD_ENTRIES: [(Position, char); 11] = [
@@ -87,13 +88,7 @@ fn get_keypad_paths_cached(
     cache: &mut PathsCache,
     keypad: &Keypad,
 ) -> Vec<Path> {
-    if let Some(paths) = cache.get(&(from, to)) {
-        paths.clone()
-    } else {
-        let paths = keypad_paths(from, to, keypad);
-        cache.insert((from, to), paths.clone());
-        paths
-    }
+    cache.get_or_compute((from, to), |_| keypad_paths(from, to, keypad))
 }
 
 // For each code, return all possible shortest paths of buttons to press on the given keypad
@@ -169,27 +164,23 @@ fn recurse(
     counts_cache: &mut CountsCache,
     keypad: &Keypad,
 ) -> usize {
-    if let Some(cached) = counts_cache.get(&(from, to, layer)) {
-        *cached
-    } else if layer == 1 {
-        let result = get_keypad_paths_cached(from, to, paths_cache, keypad)[0].len();
-        counts_cache.insert((from, to, layer), result);
-        result
-    } else {
-        let result = get_keypad_paths_cached(from, to, paths_cache, keypad)
-            .into_iter()
-            .map(|mut p| {
-                // Each robot/layer starts on A
-                p.insert(0, 'A');
-                p.windows(2)
-                    .map(|w| recurse(w[0], w[1], layer - 1, paths_cache, counts_cache, keypad))
-                    .sum()
-            })
-            .min()
-            .unwrap();
-        counts_cache.insert((from, to, layer), result);
-        result
-    }
+    counts_cache.get_or_compute((from, to, layer), |counts_cache| {
+        if layer == 1 {
+            get_keypad_paths_cached(from, to, paths_cache, keypad)[0].len()
+        } else {
+            get_keypad_paths_cached(from, to, paths_cache, keypad)
+                .into_iter()
+                .map(|mut p| {
+                    // Each robot/layer starts on A
+                    p.insert(0, 'A');
+                    p.windows(2)
+                        .map(|w| recurse(w[0], w[1], layer - 1, paths_cache, counts_cache, keypad))
+                        .sum()
+                })
+                .min()
+                .unwrap()
+        }
+    })
 }
 
 // Instead of generating all possible paths for all layers, keep track of the number of key presses for each layer.
@@ -240,8 +231,8 @@ fn complexity(code: &str, nr_button_presses: usize) -> usize {
 // in order to cause the robot in front of the door to type each code.
 // What is the sum of the complexities of the five codes on your list?
 pub fn part_1(input: &str) -> usize {
-    let mut paths_cache: PathsCache = HashMap::new();
-    let mut counts_cache: CountsCache = HashMap::new();
+    let mut paths_cache: PathsCache = Memo::new();
+    let mut counts_cache: CountsCache = Memo::new();
 
     input
         .lines()
@@ -254,8 +245,8 @@ pub fn part_1(input: &str) -> usize {
 
 // Part 1 but with 25 layers of directional keypad robots
 pub fn part_2(input: &str) -> usize {
-    let mut paths_cache: PathsCache = HashMap::new();
-    let mut counts_cache: CountsCache = HashMap::new();
+    let mut paths_cache: PathsCache = Memo::new();
+    let mut counts_cache: CountsCache = Memo::new();
 
     input
         .lines()
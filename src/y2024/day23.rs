@@ -1,7 +1,7 @@
-use std::collections::{HashMap, HashSet};
-use std::fs::File;
-use std::io::Write;
-use std::usize;
+use crate::util::graph::Graph;
+use crate::util::layout::{force_directed, graph_to_svg, GraphStyle};
+use crate::util::visualize::Visualizer;
+use std::collections::HashSet;
 
 fn parse_connections(input: &str) -> Vec<(&str, &str)> {
     input
@@ -10,74 +10,26 @@ fn parse_connections(input: &str) -> Vec<(&str, &str)> {
         .collect()
 }
 
-// Connections are bidirectional
-fn build_graph(connections: &[(&str, &str)]) -> HashMap<String, HashSet<String>> {
-    let mut graph: HashMap<String, HashSet<String>> = HashMap::new();
-    for (left, right) in connections {
-        graph
-            .entry(left.to_string())
-            .or_default()
-            .insert(right.to_string());
-        graph
-            .entry(right.to_string())
-            .or_default()
-            .insert(left.to_string());
+fn parse_graph(input: &str) -> Graph {
+    let mut graph = Graph::undirected();
+    for (left, right) in parse_connections(input) {
+        graph.add_edge(left, right);
     }
     graph
 }
 
-// Write dot file -> visualize with `dot -Kneato -Tpng -o output.png input.dot`
-#[allow(dead_code)]
-fn write_to_dot(edges: &Vec<(String, String)>, file_suffix: &str) -> std::io::Result<()> {
-    let mut dot_content = String::new();
-
-    dot_content.push_str("graph {\n");
-    edges.iter().for_each(|(from, to)| {
-        dot_content.push_str(&format!("  {} -- {};\n", from, to));
-    });
-    dot_content.push_str("}\n");
-
-    File::create(format!("outputs/2024/day23{}.dot", file_suffix))?
-        .write_all(dot_content.as_bytes())?;
-    Ok(())
-}
-
-#[allow(dead_code)]
-fn write_graph_to_dot(graph: &HashMap<String, HashSet<String>>) {
-    let edges: HashSet<(String, String)> = graph
-        .iter()
-        .flat_map(|(l, r)| {
-            r.iter().map(|n| {
-                let mut edge = vec![l.to_string(), n.to_string()];
-                edge.sort();
-                (
-                    edge.get(0).unwrap().to_string(),
-                    edge.get(1).unwrap().to_string(),
-                )
-            })
-        })
-        .collect();
-    let _ = write_to_dot(&edges.into_iter().collect(), "");
-}
-
 // Find all the sets of three inter-connected computers.
 // How many contain at least one computer with a name that starts with t?
 pub fn part_1(input: &str) -> usize {
-    let connections = parse_connections(input);
-    let graph: HashMap<String, HashSet<String>> = build_graph(&connections);
-    // write_to_dot(&connections.iter().map(|(l, r)| (l.to_string(), r.to_string())).collect(), "");
+    let graph = parse_graph(input);
 
-    let mut connected_sets: HashSet<Vec<String>> = HashSet::new();
-    for (from, targets) in graph.iter().filter(|(k, _)| k.starts_with("t")) {
-        let targets: Vec<_> = targets.iter().collect();
+    let mut connected_sets: HashSet<Vec<usize>> = HashSet::new();
+    for from in (0..graph.len()).filter(|&id| graph.name(id).starts_with("t")) {
+        let targets = graph.neighbors(from);
         for (i, t) in targets.iter().enumerate() {
             for next_target in &targets[i..] {
-                if graph
-                    .get(&next_target.to_string())
-                    .unwrap()
-                    .contains(&t.to_string())
-                {
-                    let mut new_set = vec![from.clone(), t.to_string(), next_target.to_string()];
+                if graph.neighbors(*next_target).contains(t) {
+                    let mut new_set = vec![from, *t, *next_target];
                     new_set.sort();
                     connected_sets.insert(new_set);
                 }
@@ -89,70 +41,46 @@ pub fn part_1(input: &str) -> usize {
 
 // Find largest set of computers that are all connected to each other
 // -> Maximal clique problem: https://en.wikipedia.org/wiki/Clique_(graph_theory)
-// Could search from the biggest possible group size -> start from nodes with largest nr of neighbors
-// Turned out all nodes have the same amount of neighbors, so makes no difference to sort by nr of neighbors
-// Use trick of part 1 to filter out edges that prevent creation of a clique of 3 -> you end up with "islands" of nodes
-// Can then start searching from the nodes with largest amounts of neighbors
-// If all nodes of a connected component contain the same number of neighbors -> found the max clique
+// The graph is small enough (all nodes have the same, low amount of neighbors) to list all maximal cliques
 pub fn part_2(input: &str) -> String {
-    let connections = parse_connections(input);
-    let mut graph: HashMap<String, HashSet<String>> = build_graph(&connections);
-    // write_to_dot(&connections.iter().map(|(l, r)| (l.to_string(), r.to_string())).collect(), "");
-
-    // Remove all edges which do not result in a set of three connected computers
-    let mut to_remove = Vec::new();
-    for (from, neighbors) in &graph {
-        for to in neighbors.iter() {
-            let neighbors_of_to = graph.get(to).unwrap();
-            // If none of the neighbors of 'to' point back to 'from', can remove the connection
-            if !neighbors_of_to.iter().any(|n| {
-                graph
-                    .get(&n.to_string())
-                    .unwrap()
-                    .contains(&from.to_string())
-            }) {
-                to_remove.push((from.to_string(), to.to_string()));
-                break;
-            }
-        }
-    }
-    // println!("Removing {} edges", to_remove.len());
-    for (from, to) in to_remove {
-        // println!("{} had {} neighbors",from,graph.get(&from.to_string()).unwrap().len());
-        // println!("{} had {} neighbors",to,graph.get(&to.to_string()).unwrap().len());
-        graph.entry(from.to_string()).and_modify(|n| {
-            n.remove(&to.to_string());
-        });
-        graph.entry(to.to_string()).and_modify(|n| {
-            n.remove(&from.to_string());
-        });
-    }
-    // write_graph_to_dot(&graph);
-
-    let mut seen: HashSet<Vec<String>> = HashSet::new();
-    let mut items: Vec<(String, HashSet<String>)> = graph.clone().into_iter().collect();
-    items.sort_by(|a, b| b.1.len().cmp(&a.1.len()));
-    for (from, targets) in &items {
-        let mut group: Vec<String> = targets.clone().into_iter().collect();
-        group.push(from.clone());
-        group.sort();
-        // Only check each potential group once
-        if !seen.insert(group.clone()) {
-            continue;
-        }
-        // println!("Checking potential group of size {} starting from {}: {:?}", targets.len(), from, &group.join(","));
-        // If all nodes of the group have the same amount of neighbors, return the result
-        let nrs_of_neighbors = group
-            .iter()
-            .map(|node| graph.get(node).unwrap().len())
-            .collect::<Vec<_>>();
-        let nr = nrs_of_neighbors[0];
-        if nrs_of_neighbors.iter().all(|x| *x == nr) {
-            return group.join(",");
-        }
-    }
+    let graph = parse_graph(input);
+
+    let largest_clique = graph
+        .maximal_cliques()
+        .into_iter()
+        .max_by_key(|clique| clique.len())
+        .expect("Graph has no nodes");
+    let mut names: Vec<&str> = largest_clique.iter().map(|&id| graph.name(id)).collect();
+    names.sort();
+    names.join(",")
+}
 
-    "No result!".to_string()
+// Network of computers with the LAN party of part 2 highlighted.
+// Also written as dot file, render with `dot -Kneato -Tpng -o network.png network.dot`
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let graph = parse_graph(input);
+    let largest_clique: HashSet<usize> = graph
+        .maximal_cliques()
+        .into_iter()
+        .max_by_key(|clique| clique.len())
+        .expect("Graph has no nodes")
+        .into_iter()
+        .collect();
+    let groups = (0..graph.len())
+        .map(|id| usize::from(largest_clique.contains(&id)))
+        .collect();
+    let highlighted_edges = graph
+        .edges()
+        .filter(|(from, to)| largest_clique.contains(from) && largest_clique.contains(to))
+        .collect();
+    let style = GraphStyle {
+        labels: graph.len() <= 50,
+        groups,
+        highlighted_edges,
+    };
+    let positions = force_directed(&graph, 300, 23);
+    visualizer.svg("network", &graph_to_svg(&graph, &positions, &style, 1000.0));
+    visualizer.graph("network", &graph);
 }
 
 #[cfg(test)]
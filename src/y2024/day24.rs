@@ -1,7 +1,14 @@
-use std::collections::HashMap;
+use crate::util::svg::{Point, Svg};
+use crate::util::visualize::Visualizer;
+use std::collections::{HashMap, HashSet};
 use std::mem::swap;
 use std::usize;
 
+// Size of the area per bit and per level of gates in the circuit diagram
+const COLUMN_WIDTH: f64 = 150.0;
+const ROW_HEIGHT: f64 = 90.0;
+const SWAPPED_COLOR: &str = "#e15759";
+
 #[derive(PartialEq, Eq, Debug)]
 enum Operation {
     AND,
@@ -18,6 +25,15 @@ impl Operation {
             _ => panic!("Invalid operation"),
         }
     }
+
+    // Fill color of the gates in the circuit diagram
+    fn color(&self) -> &'static str {
+        match self {
+            Operation::AND => "#4e79a7",
+            Operation::OR => "#f28e2b",
+            Operation::XOR => "#59a14f",
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -166,7 +182,7 @@ fn resulting_wire(gates: &Vec<(String, Gate)>, gate: &Gate) -> String {
 
 fn swap_wires(
     gates: &mut Vec<(String, Gate)>,
-    swapped: &mut Vec<String>,
+    swapped: &mut Vec<(String, String)>,
     gate_index: usize,
     expected: &str,
     actual: &str,
@@ -176,17 +192,15 @@ fn swap_wires(
     //     "swap: expected: {} at index {}, actual: {} at index {}",
     //     expected, actual, gate_index, other_index
     // );
-    swapped.push(expected.to_string());
-    swapped.push(actual.to_string());
+    swapped.push((expected.to_string(), actual.to_string()));
 
     gates[gate_index].0 = expected.to_string();
     gates[other_index].0 = actual.to_string();
 }
 
-// Your system of gates and wires has four pairs of gates which need their output wires swapped - eight wires in total.
-// Determine which four pairs of gates need their outputs swapped so that your system correctly performs addition;
-// what do you get if you sort the names of the eight wires involved in a swap and then join those names with commas?
-pub fn part_2(input: &str) -> String {
+// Swap the output wires of gates until the gates form an adder of numbers of `nr_of_bits` bits.
+// Returns the pairs of swapped wires, the first one being the wire that the gate outputs after the swap.
+fn repair_adder(gates: &mut Vec<(String, Gate)>, nr_of_bits: usize) -> Vec<(String, String)> {
     // The system seems to perform addition using a collection of full adders
     // This means we should be able to find the following operations in the input:
     // X_i XOR Y_i -> tmp_1
@@ -197,44 +211,43 @@ pub fn part_2(input: &str) -> String {
     // Special case X_0 and Y_0:
     //     * X_0 XOR Y_0 -> Z_0
     //     * X_0 AND Y_0 -> C_in_1
-    let (_, mut gates) = parse_input(input);
-    let mut swapped = Vec::<String>::new();
+    let mut swapped = Vec::new();
 
-    let mut c_in = "mqs".to_string();
+    let mut c_in = resulting_wire(gates, &Gate::from("x00", "y00", Operation::AND));
     // Iterate through each bit and ensure that gates lead to the correct wires
-    for i in 1..45 {
+    for i in 1..nr_of_bits {
         let x_i = format!("x{:0>2}", i);
         let y_i = format!("y{:0>2}", i);
         let z_i = format!("z{:0>2}", i);
         // println!("{} -> {}, {}, {}, c_in: {}", i, x_i, y_i, z_i, c_in);
         // X_i XOR Y_i -> tmp_1
         let x_xor_y = Gate::from(&x_i, &y_i, Operation::XOR);
-        let mut tmp_1 = resulting_wire(&gates, &x_xor_y);
+        let mut tmp_1 = resulting_wire(gates, &x_xor_y);
 
         // X_i AND Y_i -> tmp_3
         let x_and_y = Gate::from(&x_i, &y_i, Operation::AND);
-        let mut tmp_3 = resulting_wire(&gates, &x_and_y);
+        let mut tmp_3 = resulting_wire(gates, &x_and_y);
 
         // Each iteration, we know which combination of wires leads to z_i. If the next wire
         // does not result in z_i, then swap it with z_i so rest of the checks can finish successfully
         // tmp_1 XOR C_in_i -> Z_i
         let tmp1_xor_cin = Gate::from(&tmp_1, &c_in, Operation::XOR);
-        let i = gate_index(&gates, &tmp1_xor_cin).unwrap_or_else(|| {
+        let i = gate_index(gates, &tmp1_xor_cin).unwrap_or_else(|| {
             // Gate using tmp1 could not be found. -> Must have been swapped with tmp3
             // println!("Swapping tmp1 and tmp3");
-            let tmp_1_i = gate_index(&gates, &x_xor_y).expect("x_xor_y");
-            swap_wires(&mut gates, &mut swapped, tmp_1_i, &tmp_1, &tmp_3);
+            let tmp_1_i = gate_index(gates, &x_xor_y).expect("x_xor_y");
+            swap_wires(gates, &mut swapped, tmp_1_i, &tmp_1, &tmp_3);
             swap(&mut tmp_1, &mut tmp_3);
             // Search again after swapping
             let tmp1_xor_cin = Gate::from(&tmp_1, &c_in, Operation::XOR);
-            gate_index(&gates, &tmp1_xor_cin)
+            gate_index(gates, &tmp1_xor_cin)
                 .expect("Could not find tmp1_xor_cin even after swapping")
         });
 
         let actual_wire = gates[i].0.clone();
         if actual_wire != z_i {
             // Expected z_i but found something else -> swap
-            swap_wires(&mut gates, &mut swapped, i, &z_i, &actual_wire);
+            swap_wires(gates, &mut swapped, i, &z_i, &actual_wire);
             if tmp_3 == z_i {
                 tmp_3 = actual_wire;
             }
@@ -242,19 +255,215 @@ pub fn part_2(input: &str) -> String {
 
         // tmp_1 AND C_in_i -> tmp_2
         let tmp1_and_c = Gate::from(&tmp_1, &c_in, Operation::AND);
-        let tmp_2 = resulting_wire(&gates, &tmp1_and_c);
+        let tmp_2 = resulting_wire(gates, &tmp1_and_c);
 
         // tmp_2 OR tmp_3 -> C_in_i+1
         let tmp2_or_tmp3 = Gate::from(&tmp_2, &tmp_3, Operation::OR);
-        let next_c_in = resulting_wire(&gates, &tmp2_or_tmp3);
+        let next_c_in = resulting_wire(gates, &tmp2_or_tmp3);
 
         c_in = next_c_in.clone();
     }
 
+    swapped
+}
+
+// Your system of gates and wires has four pairs of gates which need their output wires swapped - eight wires in total.
+// Determine which four pairs of gates need their outputs swapped so that your system correctly performs addition;
+// what do you get if you sort the names of the eight wires involved in a swap and then join those names with commas?
+pub fn part_2(input: &str) -> String {
+    let (wires, mut gates) = parse_input(input);
+    let nr_of_bits = wires.keys().filter(|wire| wire.starts_with('x')).count();
+    let mut swapped: Vec<String> = repair_adder(&mut gates, nr_of_bits)
+        .into_iter()
+        .flat_map(|(a, b)| [a, b])
+        .collect();
     swapped.sort();
     swapped.join(",")
 }
 
+// Bit and level of a wire in the circuit diagram. Input wires x_i and y_i are on level 0 of bit i.
+// Gates belong to the highest bit of their inputs, one level below the inputs of that same bit,
+// so every bit of a ripple carry adder gets the same shape and the carry runs from one bit into the next.
+fn wire_position(
+    wire: &str,
+    gates: &HashMap<&str, &Gate>,
+    positions: &mut HashMap<String, (usize, usize)>,
+) -> (usize, usize) {
+    if let Some(&position) = positions.get(wire) {
+        return position;
+    }
+    let position = match gates.get(wire) {
+        Some(gate) => {
+            let a = wire_position(&gate.a, gates, positions);
+            let b = wire_position(&gate.b, gates, positions);
+            let bit = a.0.max(b.0);
+            let level = [a, b]
+                .iter()
+                .filter(|(input_bit, _)| *input_bit == bit)
+                .map(|(_, level)| level + 1)
+                .max()
+                .unwrap_or(1);
+            (bit, level)
+        }
+        None => (
+            wire[1..].parse().expect("Input wire without a bit number"),
+            0,
+        ),
+    };
+    positions.insert(wire.to_string(), position);
+    position
+}
+
+// Position in the diagram of every input wire and of every gate, by the wire that it outputs.
+// Gates on the same bit and level are put next to each other, sorted by operation.
+fn circuit_layout(gates: &[(String, Gate)]) -> HashMap<String, Point> {
+    let outputs: HashMap<&str, &Gate> = gates.iter().map(|(w, g)| (w.as_str(), g)).collect();
+    let mut positions = HashMap::new();
+    for (wire, _) in gates {
+        wire_position(wire, &outputs, &mut positions);
+    }
+
+    let mut cells: HashMap<(usize, usize), Vec<(String, String)>> = HashMap::new();
+    for (wire, position) in positions {
+        let op = outputs.get(wire.as_str()).map(|g| format!("{:?}", g.op));
+        cells
+            .entry(position)
+            .or_default()
+            .push((op.unwrap_or_default(), wire));
+    }
+
+    let mut layout = HashMap::new();
+    for ((bit, level), mut wires) in cells {
+        wires.sort();
+        let width = COLUMN_WIDTH / wires.len() as f64;
+        for (i, (_, wire)) in wires.into_iter().enumerate() {
+            let x = bit as f64 * COLUMN_WIDTH + (i as f64 + 0.5) * width;
+            let y = (level as f64 + 0.5) * ROW_HEIGHT;
+            layout.insert(wire, (x, y));
+        }
+    }
+    layout
+}
+
+// Gates colored by operation, the gates whose output was swapped and the wires leaving them are drawn in red
+fn circuit_to_svg(gates: &[(String, Gate)], swapped: &[(String, String)]) -> Svg {
+    let layout = circuit_layout(gates);
+    let swapped_with: HashMap<&str, &str> = swapped
+        .iter()
+        .flat_map(|(a, b)| [(a.as_str(), b.as_str()), (b.as_str(), a.as_str())])
+        .collect();
+    let outputs: HashMap<&str, &Gate> = gates.iter().map(|(w, g)| (w.as_str(), g)).collect();
+    let mut wires: Vec<&String> = layout.keys().collect();
+    wires.sort();
+    let (width, height) = layout
+        .values()
+        .fold((0.0, 0.0), |(w, h): (f64, f64), (x, y)| {
+            (w.max(*x), h.max(*y))
+        });
+    let mut svg = Svg::new(width + COLUMN_WIDTH / 2.0, height + ROW_HEIGHT / 2.0);
+    svg.background("white");
+
+    for (wire, gate) in gates {
+        let (x, y) = layout[wire];
+        for input in [&gate.a, &gate.b] {
+            let (from_x, from_y) = layout[input];
+            let (color, width) = match swapped_with.contains_key(input.as_str()) {
+                true => (SWAPPED_COLOR, 2.5),
+                false => ("#bbbbbb", 1.0),
+            };
+            svg.line((from_x, from_y + 14.0), (x, y - 14.0), color, width);
+        }
+    }
+    for wire in wires {
+        let (x, y) = layout[wire];
+        match outputs.get(wire.as_str()) {
+            Some(gate) => {
+                let stroke = match swapped_with.contains_key(wire.as_str()) {
+                    true => (SWAPPED_COLOR, 3.0),
+                    false => ("none", 0.0),
+                };
+                svg.rectangle(
+                    (x - 28.0, y - 14.0),
+                    (x + 28.0, y + 14.0),
+                    gate.op.color(),
+                    stroke.0,
+                    stroke.1,
+                );
+                let mut title = format!("{} {:?} {} -> {}", gate.a, gate.op, gate.b, wire);
+                if let Some(other) = swapped_with.get(wire.as_str()) {
+                    title.push_str(&format!(", swapped with {}", other));
+                }
+                svg.title(&title);
+                svg.text((x, y), &format!("{:?}", gate.op), 11.0, "white");
+                svg.text((x, y + 22.0), wire, 10.0, "#333333");
+            }
+            None => {
+                svg.circle((x, y), 14.0, "#dddddd");
+                svg.title(wire);
+                svg.text((x, y), wire, 10.0, "#333333");
+            }
+        }
+    }
+    svg
+}
+
+// Same diagram as DOT, with the positions pinned -> render with `neato -n -Tsvg`
+fn circuit_to_dot(gates: &[(String, Gate)], swapped: &[(String, String)]) -> String {
+    let layout = circuit_layout(gates);
+    let swapped: HashSet<&str> = swapped
+        .iter()
+        .flat_map(|(a, b)| [a.as_str(), b.as_str()])
+        .collect();
+    let outputs: HashMap<&str, &Gate> = gates.iter().map(|(w, g)| (w.as_str(), g)).collect();
+    let mut wires: Vec<&String> = layout.keys().collect();
+    wires.sort();
+
+    let mut lines = vec!["digraph circuit {".to_string()];
+    lines.push("  node [style=filled, fontname=monospace, fontsize=10];".to_string());
+    for wire in wires {
+        let (x, y) = layout[wire];
+        // DOT has y pointing up
+        let position = format!("pos=\"{:.0},{:.0}!\"", x, -y);
+        let node = match outputs.get(wire.as_str()) {
+            Some(gate) => format!(
+                "shape=box, label=\"{:?}\\n{}\", fillcolor=\"{}\", fontcolor=white{}",
+                gate.op,
+                wire,
+                gate.op.color(),
+                if swapped.contains(wire.as_str()) {
+                    format!(", color=\"{}\", penwidth=3", SWAPPED_COLOR)
+                } else {
+                    String::new()
+                }
+            ),
+            None => "shape=circle, fillcolor=\"#dddddd\"".to_string(),
+        };
+        lines.push(format!("  {} [{}, {}];", wire, node, position));
+    }
+    for (wire, gate) in gates {
+        for input in [&gate.a, &gate.b] {
+            match swapped.contains(input.as_str()) {
+                true => lines.push(format!(
+                    "  {} -> {} [color=\"{}\", penwidth=2];",
+                    input, wire, SWAPPED_COLOR
+                )),
+                false => lines.push(format!("  {} -> {};", input, wire)),
+            }
+        }
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+// Diagram of the repaired adder laid out by bit, with the wires that part 2 swapped highlighted
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let (wires, mut gates) = parse_input(input);
+    let nr_of_bits = wires.keys().filter(|wire| wire.starts_with('x')).count();
+    let swapped = repair_adder(&mut gates, nr_of_bits);
+    visualizer.svg("circuit", &circuit_to_svg(&gates, &swapped));
+    visualizer.dot("circuit", &circuit_to_dot(&gates, &swapped));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -325,6 +534,65 @@ tnw OR pbm -> gnj";
         assert_eq!(part_1(EXAMPLE_INPUT_2), 2024);
     }
 
+    // Ripple carry adder of `x` and `y` with `bits` bits, with the outputs of the `swaps` gates swapped
+    fn adder(x: usize, y: usize, bits: usize, swaps: &[(&str, &str)]) -> String {
+        let mut lines: Vec<String> = (0..bits)
+            .map(|i| format!("x{:02}: {}", i, x >> i & 1))
+            .chain((0..bits).map(|i| format!("y{:02}: {}", i, y >> i & 1)))
+            .collect();
+        lines.push(String::new());
+        lines.push("x00 XOR y00 -> z00".to_string());
+        lines.push("x00 AND y00 -> c01".to_string());
+        for i in 1..bits {
+            let carry_out = match i + 1 == bits {
+                true => format!("z{:02}", bits),
+                false => format!("c{:02}", i + 1),
+            };
+            lines.push(format!("x{:02} XOR y{:02} -> a{:02}", i, i, i));
+            lines.push(format!("x{:02} AND y{:02} -> b{:02}", i, i, i));
+            lines.push(format!("a{:02} XOR c{:02} -> z{:02}", i, i, i));
+            lines.push(format!("a{:02} AND c{:02} -> d{:02}", i, i, i));
+            lines.push(format!("d{:02} OR b{:02} -> {}", i, i, carry_out));
+        }
+        let mut input = lines.join("\n");
+        for (a, b) in swaps {
+            input = input
+                .replace(&format!("-> {}", a), "-> tmp")
+                .replace(&format!("-> {}", b), &format!("-> {}", a))
+                .replace("-> tmp", &format!("-> {}", b));
+        }
+        input
+    }
+
+    #[test]
+    fn test_part_2_adder() {
+        assert_eq!(part_1(&adder(11, 6, 4, &[])), 17);
+        let input = adder(11, 6, 4, &[("z02", "d02"), ("a03", "b03")]);
+        assert_eq!(part_2(&input), "a03,b03,d02,z02");
+    }
+
+    #[test]
+    fn test_circuit_layout() {
+        let (_, mut gates) = parse_input(&adder(0, 0, 3, &[("z02", "d02")]));
+        let swapped = repair_adder(&mut gates, 3);
+        assert_eq!(swapped, vec![("z02".to_string(), "d02".to_string())]);
+
+        let layout = circuit_layout(&gates);
+        // Inputs on top, every bit in its own column, the carry out of bit 2 is its lowest gate
+        assert_eq!(layout["x01"], (1.25 * COLUMN_WIDTH, 0.5 * ROW_HEIGHT));
+        assert_eq!(layout["a01"], (1.75 * COLUMN_WIDTH, 1.5 * ROW_HEIGHT));
+        assert_eq!(layout["z02"], (2.75 * COLUMN_WIDTH, 2.5 * ROW_HEIGHT));
+        assert_eq!(layout["z03"], (2.5 * COLUMN_WIDTH, 3.5 * ROW_HEIGHT));
+
+        let svg = circuit_to_svg(&gates, &swapped).to_string();
+        assert!(svg.contains("a02 XOR c02 -&gt; z02, swapped with d02"));
+        // Outlines of the 2 swapped gates and the wire from d02 into the carry
+        let stroke = format!("stroke=\"{}\"", SWAPPED_COLOR);
+        assert_eq!(svg.matches(&stroke).count(), 3);
+        let dot = circuit_to_dot(&gates, &swapped);
+        assert!(dot.contains("d02 -> z03 [color="));
+    }
+
     #[test]
     fn test_part_1() {
         assert_eq!(part_1(&load_input("inputs/2024/day_24")), 42410633905894);
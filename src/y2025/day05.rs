@@ -1,3 +1,7 @@
+use crate::util::ranges::RangeSet;
+use rand::rngs::StdRng;
+use rand::Rng;
+
 // How many of the available ingredient IDs are fresh?
 pub fn part_1(input: &str) -> usize {
     let (ranges, ids) = input
@@ -10,30 +14,21 @@ pub fn part_1(input: &str) -> usize {
             line.parse::<usize>()
                 .expect("Could not parse ingredient ID")
         })
-        .filter(|id| is_fresh(id, &ranges))
+        .filter(|id| ranges.contains(id))
         .count()
 }
 
-fn parse_ranges(input: &str) -> Vec<(usize, usize)> {
+// Ranges in the input are inclusive on both ends, and may overlap each other
+fn parse_ranges(input: &str) -> RangeSet<usize> {
     input
         .lines()
         .map(|line| {
             let (start, end) = line.split_once("-").expect("Could not split range on '-'");
-            (
-                start.parse().expect("Could not parse start of range"),
-                end.parse().expect("Could not parse end of range"),
-            )
+            let start: usize = start.parse().expect("Could not parse start of range");
+            let end: usize = end.parse().expect("Could not parse end of range");
+            std::iter::once(start..end + 1).collect::<RangeSet<_>>()
         })
-        .collect()
-}
-
-fn is_fresh(id: &usize, ranges: &Vec<(usize, usize)>) -> bool {
-    for (start, end) in ranges {
-        if start <= id && id <= end {
-            return true;
-        }
-    }
-    false
+        .fold(RangeSet::new(), |fresh, range| fresh.union(&range))
 }
 
 // How many possible ingredient IDs could be considered fresh?
@@ -41,42 +36,23 @@ pub fn part_2(input: &str) -> usize {
     let (ranges, _) = input
         .split_once("\n\n")
         .expect("Could not split input in 2 parts");
-    let ranges = parse_ranges(ranges);
-    let ranges = merge_overlapping_ranges(ranges);
-    ranges.iter().map(|(start, end)| end - start + 1).sum()
+    parse_ranges(ranges).len()
 }
 
-fn merge_overlapping_ranges(ranges: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
-    let mut new_ranges = Vec::new();
-
-    for (start, end) in ranges {
-        if let Some(p) = new_ranges
-            .iter()
-            .position(|(s, e)| overlaps((start, end), (*s, *e)))
-        {
-            let (prev_start, prev_end) = new_ranges[p];
-            new_ranges[p] = merge_ranges(prev_start, prev_end, start, end);
-            new_ranges = merge_overlapping_ranges(new_ranges);
-        } else {
-            new_ranges.push((start, end));
-        }
-    }
-
-    new_ranges
-}
-
-fn merge_ranges(prev_start: usize, prev_end: usize, start: usize, end: usize) -> (usize, usize) {
-    let new_start = if prev_start < start {
-        prev_start
-    } else {
-        start
-    };
-    let new_end = if prev_end < end { end } else { prev_end };
-    (new_start, new_end)
-}
-
-fn overlaps(a: (usize, usize), b: (usize, usize)) -> bool {
-    a.0 <= b.1 && b.0 <= a.1
+// Random database with `size` possibly overlapping fresh ID ranges and `size` available IDs
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    const MAX_ID: usize = 500_000_000_000_000;
+    let ranges: Vec<String> = (0..size)
+        .map(|_| {
+            let start = rng.gen_range(1..MAX_ID);
+            let length = rng.gen_range(0..MAX_ID / size.max(1));
+            format!("{}-{}", start, (start + length).min(MAX_ID))
+        })
+        .collect();
+    let ids: Vec<String> = (0..size)
+        .map(|_| rng.gen_range(1..=MAX_ID).to_string())
+        .collect();
+    format!("{}\n\n{}", ranges.join("\n"), ids.join("\n"))
 }
 
 #[cfg(test)]
@@ -1,7 +1,13 @@
+use crate::util::gif::Palette;
+use crate::util::terminal::TerminalAnimation;
+use crate::util::visualize::Visualizer;
 use std::collections::HashMap;
 
-// How many times does the beam get split?
-pub fn part_1(input: &str) -> usize {
+fn count_splits(
+    input: &str,
+    visualizer: &mut Visualizer,
+    mut animation: Option<&mut TerminalAnimation>,
+) -> usize {
     let mut lines = input.lines();
     let s = lines
         .next()
@@ -10,9 +16,15 @@ pub fn part_1(input: &str) -> usize {
         .expect("Could not find starting point");
     let mut beams = vec![s];
     let mut splits = 0;
+    // Rows of the input with the beams drawn in, only kept when something shows them
+    let draw = visualizer.is_enabled() || animation.is_some();
+    let mut diagram: Vec<String> = Vec::new();
+    if draw {
+        diagram.extend(input.lines().take(1).map(str::to_string));
+    }
 
     // Split beams from top to bottom while iterating through the input
-    for line in lines {
+    for (row, line) in lines.enumerate() {
         let splitters: Vec<usize> = line
             .char_indices()
             .filter_map(|(i, c)| if c == '^' { Some(i) } else { None })
@@ -38,25 +50,42 @@ pub fn part_1(input: &str) -> usize {
         }
         beams = next_beams;
 
-        // println!(
-        //     "{}",
-        //     (0..line.len())
-        //         .map(|i| {
-        //             if splitters.contains(&i) {
-        //                 '^'
-        //             } else if beams.contains(&i) {
-        //                 '|'
-        //             } else {
-        //                 '.'
-        //             }
-        //         })
-        //         .collect::<String>()
-        // );
+        if draw {
+            diagram.push(
+                (0..line.len())
+                    .map(|i| {
+                        if splitters.contains(&i) {
+                            '^'
+                        } else if beams.contains(&i) {
+                            '|'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect(),
+            );
+        }
+        if let Some(animation) = animation.as_deref_mut() {
+            // Rows the beams did not reach yet are shown as in the input
+            animation.frame_with(|| {
+                let rest: Vec<&str> = input.lines().skip(row + 2).collect();
+                [diagram.join("\n"), rest.join("\n")].join("\n")
+            });
+        }
     }
 
+    visualizer.text("beams", &diagram.join("\n"));
+    if let Some(animation) = animation {
+        animation.finish(&diagram.join("\n"));
+    }
     splits
 }
 
+// How many times does the beam get split?
+pub fn part_1(input: &str) -> usize {
+    count_splits(input, &mut Visualizer::disabled(), None)
+}
+
 // Now it's the timeline that splits instead of the beam. How many possible timelines are there?
 pub fn part_2(input: &str) -> usize {
     let mut lines = input.lines();
@@ -97,6 +126,21 @@ fn merge_beam_counts(next_beams: &mut HashMap<usize, usize>, beam: usize, count:
         .or_insert(count);
 }
 
+// Draws the beams going through the splitters
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    count_splits(input, visualizer, None);
+}
+
+// Beams going down through the splitters, one row per frame
+pub fn animate(input: &str, animation: &mut TerminalAnimation) {
+    animation.set_palette(Palette::new(&[
+        ('S', [255, 60, 60]),
+        ('|', [255, 200, 40]),
+        ('^', [90, 160, 255]),
+    ]));
+    count_splits(input, &mut Visualizer::disabled(), Some(animation));
+}
+
 #[cfg(test)]
 mod tests {
     use crate::util::util::load_input;
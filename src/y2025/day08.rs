@@ -1,4 +1,5 @@
-use std::{collections::HashMap, mem::swap};
+use crate::util::dsu::DisjointSet;
+use crate::util::visualize::Visualizer;
 
 type Box3D = [u32; 3];
 
@@ -9,26 +10,27 @@ pub fn part_1(input: &str) -> u64 {
 
 fn part_1_for_n_pairs(input: &str, n: usize) -> u64 {
     let boxes = parse_input(input);
-    let mut distances = calculate_distances(&boxes);
+    let circuits = connect_closest_pairs(&boxes, n);
+
+    // Multiply the three largest circuits
+    circuits
+        .component_sizes()
+        .into_iter()
+        .take(3)
+        .map(|size| size as u64)
+        .product()
+}
+
+// Collect the n closest pairs of boxes into circuits
+fn connect_closest_pairs(boxes: &[Box3D], n: usize) -> DisjointSet {
+    let mut distances = calculate_distances(boxes);
     distances.select_nth_unstable(n); // sort until nth index
 
-    // Collect closest pairs of boxes into circuits. Map box_id -> circuit_id
-    let mut circuits: HashMap<u16, u16> = HashMap::new();
-    let mut next_id = 0;
+    let mut circuits = DisjointSet::new(boxes.len());
     for dist in &distances[..n] {
-        connect_pairs(&mut circuits, &mut next_id, dist.i, dist.j);
-    }
-
-    // Determine size of each circuit
-    let mut circuit_sizes: HashMap<u16, u16> = HashMap::new();
-    for id in circuits.values() {
-        *circuit_sizes.entry(*id).or_insert(0) += 1;
+        circuits.union(dist.i as usize, dist.j as usize);
     }
-
-    // Multiply the three largest circuits
-    let mut sizes: Vec<_> = circuit_sizes.values().collect();
-    sizes.sort_by(|a, b| b.cmp(&a));
-    sizes.into_iter().take(3).map(|i| *i as u64).product()
+    circuits
 }
 
 fn parse_input(input: &str) -> Vec<Box3D> {
@@ -76,91 +78,41 @@ fn calculate_distances(boxes: &[Box3D]) -> Vec<Dist> {
     distances
 }
 
-fn connect_pairs(circuits: &mut HashMap<u16, u16>, next_id: &mut u16, i: u16, j: u16) -> bool {
-    match (circuits.get(&i), circuits.get(&j)) {
-        (Some(&circuit_i), Some(&circuit_j)) => {
-            if circuit_i != circuit_j {
-                // Merge circuits
-                for v in circuits.values_mut() {
-                    if *v == circuit_j {
-                        *v = circuit_i;
-                    }
-                }
-                true
-            } else {
-                false
-            }
-        }
-        (Some(&circuit_i), None) => {
-            circuits.insert(j, circuit_i);
-            true
-        }
-        (None, Some(&circuit_j)) => {
-            circuits.insert(i, circuit_j);
-            true
-        }
-        _ => {
-            circuits.insert(i, *next_id);
-            circuits.insert(j, *next_id);
-            *next_id += 1;
-            true
-        }
-    }
-}
-
 // Keep connecting boxes until they're all in the same circuit.
 // What do you get if you multiply together the X coordinates of the last two junction boxes you need to connect?
 pub fn part_2(input: &str) -> u64 {
     let boxes = parse_input(input);
+    // A single box is a circuit by itself, there is no last pair to connect
+    if boxes.len() < 2 {
+        return 0;
+    }
     let mut distances = calculate_distances(&boxes);
     // distances.sort_by(|left, right| right.d.cmp(&left.d));
     distances.sort_unstable_by_key(|d| d.d);
 
-    // Disjoint-set/union-find (DSU) approach to connect circuits - https://en.wikipedia.org/wiki/Disjoint-set_data_structure
-    // Circuits = vec where each index (junction box) points to the set/circuit it is in
-    let mut circuits: Vec<u16> = (0..boxes.len()).map(|i| i as u16).collect();
-    let mut sizes: Vec<u16> = vec![1; boxes.len()];
-    let mut result = 0;
-    let mut unions = 0;
+    // Disjoint-set/union-find (DSU) approach to connect circuits
+    let mut circuits = DisjointSet::new(boxes.len());
     for dist in distances {
-        let mut circuit_i = find_circuit(dist.i, &mut circuits) as usize;
-        let mut circuit_j = find_circuit(dist.j, &mut circuits) as usize;
-
-        if circuit_i != circuit_j {
-            if sizes[circuit_i] < sizes[circuit_j] {
-                swap(&mut circuit_i, &mut circuit_j);
-            }
-
-            circuits[circuit_j] = circuit_i as u16;
-            sizes[circuit_i] += sizes[circuit_j];
-            result = boxes[dist.i as usize][0] as u64 * boxes[dist.j as usize][0] as u64;
-            unions += 1;
-            if unions == boxes.len() - 1 {
-                return result;
-            }
+        if circuits.union(dist.i as usize, dist.j as usize) && circuits.component_count() == 1 {
+            return boxes[dist.i as usize][0] as u64 * boxes[dist.j as usize][0] as u64;
         }
     }
 
-    result
+    unreachable!("Connected all pairs but boxes are not in a single circuit")
 }
 
-// Non-recursive find with path compression
-#[inline(always)]
-fn find_circuit(i: u16, circuits: &mut [u16]) -> u16 {
-    // Find root (= circuit_id)
-    let mut root = i;
-    while circuits[root as usize] != root {
-        root = circuits[root as usize];
-    }
-
-    // Set all nodes in the path to the root value
-    let mut x = i;
-    while circuits[x as usize] != x {
-        let next = circuits[x as usize];
-        circuits[x as usize] = root;
-        x = next;
-    }
-    root
+// List the boxes of the largest circuit after connecting the 1000 closest pairs
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let boxes = parse_input(input);
+    let mut circuits = connect_closest_pairs(&boxes, 1000);
+    let largest = (0..boxes.len())
+        .max_by_key(|&i| circuits.size_of(i))
+        .expect("No junction boxes");
+    let lines: String = circuits
+        .members(largest)
+        .map(|i| format!("{},{},{}\n", boxes[i][0], boxes[i][1], boxes[i][2]))
+        .collect();
+    visualizer.text("largest_circuit", &lines);
 }
 
 #[cfg(test)]
@@ -205,6 +157,11 @@ mod tests {
         assert_eq!(part_2(EXAMPLE_INPUT_1), 25272);
     }
 
+    #[test]
+    fn test_part_2_single_box() {
+        assert_eq!(part_2("162,817,812"), 0);
+    }
+
     #[test]
     fn test_part_2() {
         assert_eq!(part_2(&load_input("inputs/2025/day_8")), 7893123992);
@@ -1,20 +1,32 @@
-use std::collections::HashSet;
+use crate::util::bits::VisitedGrid;
+use crate::util::compress::{Axis, CompressedGrid};
+use crate::util::polygon;
+use crate::util::svg::Svg;
+use crate::util::visualize::Visualizer;
 
 type Point = (u32, u32);
 
-// What is the largest area of any rectangle you can make?
-pub fn part_1(input: &str) -> usize {
-    let corners: Vec<Point> = parse_input(input);
+// Area and opposite corners of the largest rectangle
+type Rectangle = (usize, Point, Point);
 
-    let mut max = 0;
+fn largest_rectangle(corners: &[Point]) -> Rectangle {
+    let mut max = (0, corners[0], corners[0]);
     for i in 0..(corners.len() - 1) {
         for j in i + 1..corners.len() {
-            max = max.max(area(&corners[i], &corners[j]));
+            let area = area(&corners[i], &corners[j]);
+            if area > max.0 {
+                max = (area, corners[i], corners[j]);
+            }
         }
     }
     max
 }
 
+// What is the largest area of any rectangle you can make?
+pub fn part_1(input: &str) -> usize {
+    largest_rectangle(&parse_input(input)).0
+}
+
 fn parse_input(input: &str) -> Vec<Point> {
     let corners: Vec<Point> = input
         .lines()
@@ -35,83 +47,143 @@ fn area(i: &Point, j: &Point) -> usize {
     dx * dy
 }
 
-// All the corners form a shape. What is the largest area of any rectangle that can be made
-// by two of the given corners while fully fitting in this shape?
-pub fn part_2(input: &str) -> usize {
-    let corners: Vec<Point> = parse_input(input);
-    // Map each position to its rank
-    // This condenses the grid from 100_000 x 100_000 to about 250 x 250
-    // With a flood fill approach we need to ensure that gaps remain between neighboring
-    // corners on the same axis, but that doesn't seem to apply for the inputs this time.
-    let mut ranks_x: Vec<u32> = corners.iter().copied().map(|(x, _)| x).collect();
-    let mut ranks_y: Vec<u32> = corners.iter().copied().map(|(_, y)| y).collect();
-    ranks_x.sort();
-    ranks_x.dedup();
-    ranks_y.sort();
-    ranks_y.dedup();
-    // println!("{}, {}", ranks_x.len(), ranks_y.len());
-    let corners: Vec<Point> = corners
-        .into_iter()
-        .map(|(x, y)| {
-            (
-                // Add 1 to leave borders of the grid empty -- this does not alter the shape
-                ranks_x.binary_search(&x).unwrap() as u32 + 1,
-                ranks_y.binary_search(&y).unwrap() as u32 + 1,
-            )
-        })
-        .collect();
+// Compressed grid of the tiles where cells are true for red or green tiles, i.e. on or inside the shape
+fn tiles_grid(corners: &[(i64, i64)]) -> CompressedGrid<bool> {
+    // Compress the grid from 100_000 x 100_000 to about 500 x 500 cells
+    // Gaps between neighboring corners on the same axis remain cells of their own, so the shape is unaltered
+    // Add an empty cell on both sides of each axis to leave borders of the grid empty for the flood fill
+    let axis = |coordinates: Vec<i64>| {
+        let min = coordinates.iter().min().unwrap() - 1;
+        let max = coordinates.iter().max().unwrap() + 1;
+        Axis::new(coordinates.into_iter().chain([min, max]))
+    };
+    let x_axis = axis(corners.iter().map(|c| c.0).collect());
+    let y_axis = axis(corners.iter().map(|c| c.1).collect());
 
     // Shape is an irregular rectilinear polygon
     // Grid == true for positions that are inside the polygon
-    let mut grid: Vec<Vec<bool>> = vec![vec![false; ranks_y.len() + 1]; ranks_x.len() + 1];
+    let mut grid = CompressedGrid::new(x_axis, y_axis, false);
     // The edges are subsequent corners in the input
     for i in 0..corners.len() {
-        let j = if i == corners.len() - 1 { 0 } else { i + 1 };
-        let (from_x, from_y) = corners[i];
-        let (to_x, to_y) = corners[j];
-        for x in from_x.min(to_x)..=from_x.max(to_x) {
-            for y in from_y.min(to_y)..=from_y.max(to_y) {
-                grid[x as usize][y as usize] = true;
-            }
-        }
+        grid.fill(corners[i], corners[(i + 1) % corners.len()], true);
     }
 
     // Mark the inside of the polygon as true
-    // print_grid(&grid);
-    flood_fill(&mut grid);
-    // print_grid(&grid);
+    flood_fill(grid.cells_mut());
+    grid
+}
+
+fn as_i64(corners: &[Point]) -> Vec<(i64, i64)> {
+    corners.iter().map(|&(x, y)| (x as i64, y as i64)).collect()
+}
 
-    let mut max = 0;
+// Rectangles from large to small, the first one that fits in the shape is the largest
+fn largest_rectangle_inside(corners: &[Point]) -> Rectangle {
+    let vertices = as_i64(corners);
+    let mut rectangles: Vec<Rectangle> = Vec::new();
     for i in 0..(corners.len() - 1) {
         for j in i + 1..corners.len() {
-            if valid_rectangle(&corners[i], &corners[j], &grid) {
-                // Get the original corner coordinates and compensate for the + 1
-                let corner1 = (
-                    ranks_x[corners[i].0 as usize - 1],
-                    ranks_y[corners[i].1 as usize - 1],
-                );
-                let corner2 = (
-                    ranks_x[corners[j].0 as usize - 1],
-                    ranks_y[corners[j].1 as usize - 1],
-                );
-                max = max.max(area(&corner1, &corner2));
-            }
+            rectangles.push((area(&corners[i], &corners[j]), corners[i], corners[j]));
         }
     }
-    max
+    // Stable sort keeps the first of equally large rectangles first
+    rectangles.sort_by_key(|rectangle| std::cmp::Reverse(rectangle.0));
+
+    let as_vertex = |(x, y): Point| (x as i64, y as i64);
+    rectangles
+        .into_iter()
+        .find(|&(_, a, b)| {
+            polygon::rectangle_inside_rectilinear(&vertices, as_vertex(a), as_vertex(b))
+        })
+        .unwrap_or((0, corners[0], corners[0]))
 }
 
-#[allow(dead_code)]
-fn print_grid(grid: &Vec<Vec<bool>>) {
-    for row in grid.clone() {
-        println!(
-            "{}",
-            row.iter()
-                .map(|b| if *b { '#' } else { '.' })
-                .collect::<String>()
-        );
+// All the corners form a shape. What is the largest area of any rectangle that can be made
+// by two of the given corners while fully fitting in this shape?
+pub fn part_2(input: &str) -> usize {
+    largest_rectangle_inside(&parse_input(input)).0
+}
+
+// Shape of red and green tiles in its original coordinates, every tile a unit square,
+// with the largest rectangles of part 1 (outline only) and part 2 (filled)
+fn tiles_to_svg(corners: &[Point], part_1: Rectangle, part_2: Rectangle) -> Svg {
+    let min = corners.iter().fold((u32::MAX, u32::MAX), |min, c| {
+        (min.0.min(c.0), min.1.min(c.1))
+    });
+    let max = corners
+        .iter()
+        .fold((0, 0), |max, c| (max.0.max(c.0), max.1.max(c.1)));
+    let mut svg = Svg::fitted(
+        (min.0 as f64, min.1 as f64),
+        ((max.0 + 1) as f64, (max.1 + 1) as f64),
+        1000.0,
+    );
+    svg.background("#111111");
+
+    let center = |&(x, y): &Point| (x as f64 + 0.5, y as f64 + 0.5);
+    let outline: Vec<(f64, f64)> = corners.iter().map(center).collect();
+    svg.polygon(&outline, "#2e7d32", "#66bb6a", 1.0);
+    let tiles = |(_, a, b): Rectangle| {
+        (
+            (a.0.min(b.0) as f64, a.1.min(b.1) as f64),
+            ((a.0.max(b.0) + 1) as f64, (a.1.max(b.1) + 1) as f64),
+        )
+    };
+    let (a, b) = tiles(part_2);
+    svg.rectangle(a, b, "#ffca2899", "#ffca28", 2.0);
+    let (a, b) = tiles(part_1);
+    svg.rectangle(a, b, "none", "#42a5f5", 2.0);
+    for corner in corners {
+        svg.circle(center(corner), 2.0 * svg.pixel(), "#e53935");
+    }
+    svg
+}
+
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let corners = parse_input(input);
+    let svg = tiles_to_svg(
+        &corners,
+        largest_rectangle(&corners),
+        largest_rectangle_inside(&corners),
+    );
+    visualizer.svg("tiles", &svg);
+    visualizer.text(
+        "compressed",
+        &compressed_to_text(
+            &tiles_grid(&as_i64(&corners)),
+            largest_rectangle_inside(&corners),
+        ),
+    );
+}
+
+// The compressed grid as text, one character per cell: # for red or green tiles, . for other tiles,
+// and O for the tiles of the given rectangle
+fn compressed_to_text(grid: &CompressedGrid<bool>, (_, a, b): Rectangle) -> String {
+    let table = grid.area_table(|&tile| tile);
+    let (from, to) = ((a.0 as i64, a.1 as i64), (b.0 as i64, b.1 as i64));
+    let mut text = format!(
+        "{} red or green tiles, {} of them in the rectangle\n",
+        table.total(),
+        table.area(from, to)
+    );
+    let in_rectangle = |x: i64, y: i64| {
+        from.0.min(to.0) <= x
+            && x <= from.0.max(to.0)
+            && from.1.min(to.1) <= y
+            && y <= from.1.max(to.1)
+    };
+    for y in 0..grid.y.len() {
+        for x in 0..grid.x.len() {
+            let (x, y) = (grid.x.coordinate(x), grid.y.coordinate(y));
+            text.push(match grid.get(x, y) {
+                Some(true) if in_rectangle(x, y) => 'O',
+                Some(true) => '#',
+                _ => '.',
+            });
+        }
+        text.push('\n');
     }
-    println!();
+    text
 }
 
 // Flood fill the inside of the polygon
@@ -122,13 +194,13 @@ fn flood_fill(grid: &mut [Vec<bool>]) {
     // Then flip grid to true for positions not visited
     let mut frontier: Vec<(usize, usize)> =
         Vec::from([(0, 0), (max_x / 2, 0), (0, max_y / 2), (max_x, max_y)]);
-    let mut visited: HashSet<(usize, usize)> = HashSet::new();
+    let mut visited = VisitedGrid::new(max_x + 1, max_y + 1);
 
     while let Some(current) = frontier.pop() {
         if visited.insert(current) {
             for neighbor in neighbors(&current, max_x, max_y) {
                 if let Some(next_pos) = neighbor {
-                    if !visited.contains(&next_pos) && !grid[next_pos.0][next_pos.1] {
+                    if !visited.contains(next_pos) && !grid[next_pos.0][next_pos.1] {
                         frontier.push(next_pos);
                     }
                 }
@@ -138,7 +210,7 @@ fn flood_fill(grid: &mut [Vec<bool>]) {
 
     for x in 0..=max_x {
         for y in 0..=max_y {
-            if !visited.contains(&(x, y)) {
+            if !visited.contains((x, y)) {
                 grid[x][y] = true;
             }
         }
@@ -162,31 +234,6 @@ fn neighbors(p: &(usize, usize), max_x: usize, max_y: usize) -> [Option<(usize,
     ]
 }
 
-// Rectangle is valid if all points on the edges are inside the grid
-fn valid_rectangle(i: &Point, j: &Point, grid: &[Vec<bool>]) -> bool {
-    let x1 = i.0.min(j.0) as usize;
-    let x2 = i.0.max(j.0) as usize;
-    let y1 = i.1.min(j.1) as usize;
-    let y2 = i.1.max(j.1) as usize;
-    let edges = [
-        (x1, y1, x1, y2),
-        (x1, y1, x2, y1),
-        (x1, y2, x2, y2),
-        (x2, y1, x2, y2),
-    ];
-
-    for e in edges {
-        for x in e.0..=e.2 {
-            for y in e.1..=e.3 {
-                if !grid[x][y] {
-                    return false;
-                }
-            }
-        }
-    }
-    true
-}
-
 #[cfg(test)]
 mod tests {
     use crate::util::util::load_input;
@@ -255,10 +302,31 @@ mod tests {
     fn test_part_2_example() {
         assert_eq!(part_2(EXAMPLE_INPUT_1), 24);
         // Other test cases have neighboring corners on same axis
-        // Special case that doesn't actually apply in the real input
-        // assert_eq!(part_2(EXAMPLE_INPUT_2), 40);
-        // assert_eq!(part_2(EXAMPLE_INPUT_3), 35);
-        // assert_eq!(part_2(EXAMPLE_INPUT_4), 66);
+        assert_eq!(part_2(EXAMPLE_INPUT_2), 40);
+        assert_eq!(part_2(EXAMPLE_INPUT_3), 35);
+        assert_eq!(part_2(EXAMPLE_INPUT_4), 66);
+    }
+
+    #[test]
+    fn test_tiles_to_svg() {
+        let corners = parse_input(EXAMPLE_INPUT_1);
+        let inside = largest_rectangle_inside(&corners);
+        assert_eq!(inside, (24, (9, 5), (2, 3)));
+        let svg = tiles_to_svg(&corners, largest_rectangle(&corners), inside).to_string();
+        assert!(svg.contains(r#"<rect x="2.00" y="3.00" width="8.00" height="3.00""#));
+        assert_eq!(svg.matches("<circle").count(), corners.len());
+    }
+
+    #[test]
+    fn test_compressed_to_text() {
+        let corners = parse_input(EXAMPLE_INPUT_1);
+        let grid = tiles_grid(&as_i64(&corners));
+        let text = compressed_to_text(&grid, largest_rectangle_inside(&corners));
+        assert!(text.starts_with(
+            "46 red or green tiles, 24 of them in the rectangle\n.........\n...#####.\n"
+        ));
+        assert_eq!(text.matches('#').count(), 22);
+        assert_eq!(text.matches('O').count(), 15);
     }
 
     #[test]
@@ -1,3 +1,7 @@
+use crate::util::bits::BitSet;
+use crate::util::linalg::{gcd, LinearSystem};
+use crate::util::memo::Memo;
+use crate::util::visualize::Visualizer;
 use std::collections::{BinaryHeap, HashMap, VecDeque};
 
 type Buttons = Vec<Vec<u8>>;
@@ -49,6 +53,14 @@ fn parse_buttons(buttons: &[&str]) -> Buttons {
         .collect()
 }
 
+// Convert buttons from nested vecs of indices to bitsets for XORing with
+fn buttons_to_bit_sets(buttons: &Buttons, nr_of_lights: usize) -> Vec<BitSet> {
+    buttons
+        .iter()
+        .map(|button| BitSet::from_indices(nr_of_lights, button.iter().map(|&i| i as usize)))
+        .collect()
+}
+
 // The least nr of button presses to set the machine to the target lights configuration
 fn fewest_button_presses_for_lights(buttons: &Buttons, lights: Lights) -> usize {
     // Each press of a button toggles the lights
@@ -57,21 +69,14 @@ fn fewest_button_presses_for_lights(buttons: &Buttons, lights: Lights) -> usize
     // Will check each possible combination, starting with each button by itself
     let mut combinations: VecDeque<Vec<usize>> = (0..buttons.len()).map(|i| vec![i]).collect();
 
-    // Convert list of booleans into one number for easy XOR
-    let target_state: u16 = lights
-        .iter()
-        .rev()
-        .fold(0, |acc, b| if *b { (acc << 1) + 1 } else { acc << 1 });
-    // Convert buttons from nested vecs of indices to vec of u16 for XORing with
-    let buttons: Vec<u16> = buttons
-        .iter()
-        .map(|button| button.iter().fold(0, |acc, i| acc + (1 << i)))
-        .collect();
+    // Convert list of booleans into a bitset for easy XOR
+    let target_state = BitSet::from_bools(&lights);
+    let buttons = buttons_to_bit_sets(buttons, lights.len());
 
     while let Some(combination) = combinations.pop_front() {
-        let mut state: u16 = 0;
+        let mut state = BitSet::new(lights.len());
         for button_index in &combination {
-            state ^= buttons[*button_index];
+            state ^= &buttons[*button_index];
         }
         if state == target_state {
             return combination.len();
@@ -112,17 +117,33 @@ pub fn part_2(input: &str) -> usize {
     input
         .lines()
         .map(|line| parse_buttons_and_joltage(line))
-        .map(|(buttons, joltage)| {
-            fewest_button_presses_for_joltage_bifurcation(
-                &buttons,
-                joltage,
-                &mut HashMap::new(),
-                &mut HashMap::new(),
-            )
-        })
+        .map(|(buttons, joltage)| fewest_button_presses_for_joltage(&buttons, joltage))
         .sum()
 }
 
+// Trying every value of the free variables is fastest when there are few of them with small bounds,
+// bifurcating is faster than trying more combinations than this
+const MAX_FREE_COMBINATIONS: i128 = 1_000;
+
+fn fewest_button_presses_for_joltage(buttons: &Buttons, joltage: Joltage) -> usize {
+    let (system, upper_bounds) = joltage_system(buttons, &joltage);
+    let combinations: i128 = system
+        .free_variables()
+        .iter()
+        .map(|&v| upper_bounds[v] + 1)
+        .product();
+    if combinations <= MAX_FREE_COMBINATIONS {
+        fewest_button_presses_for_joltage_linear_system(&system, &upper_bounds)
+    } else {
+        fewest_button_presses_for_joltage_bifurcation(
+            buttons,
+            joltage,
+            &mut Memo::new(),
+            &mut Memo::new(),
+        )
+    }
+}
+
 fn parse_buttons_and_joltage(line: &str) -> (Buttons, Joltage) {
     let parts: Vec<&str> = line.split_whitespace().collect();
     let buttons = parse_buttons(&parts[1..parts.len() - 1]);
@@ -135,6 +156,74 @@ fn parse_buttons_and_joltage(line: &str) -> (Buttons, Joltage) {
     (buttons, joltage)
 }
 
+// The system of linear equations M * x = T as described above,
+// with the upper bounds of x: each button can be pressed at most as many times as the lowest counter it increments
+fn joltage_system(buttons: &Buttons, joltage: &Joltage) -> (LinearSystem, Vec<i128>) {
+    let coefficients: Vec<Vec<i128>> = (0..joltage.len())
+        .map(|counter| {
+            buttons
+                .iter()
+                .map(|button| button.contains(&(counter as u8)) as i128)
+                .collect()
+        })
+        .collect();
+    let targets: Vec<i128> = joltage.iter().map(|&j| j as i128).collect();
+    let upper_bounds: Vec<i128> = buttons
+        .iter()
+        .map(|button| {
+            button
+                .iter()
+                .map(|&i| targets[i as usize])
+                .min()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    (LinearSystem::new(&coefficients, &targets), upper_bounds)
+}
+
+// Solve the system by trying every combination of values for its free variables
+fn fewest_button_presses_for_joltage_linear_system(
+    system: &LinearSystem,
+    upper_bounds: &[i128],
+) -> usize {
+    system
+        .min_non_negative_integer_solution(&vec![1; upper_bounds.len()], upper_bounds)
+        .map_or(0, |(presses, _)| presses as usize)
+}
+
+// The system of every machine in reduced row echelon form, each row scaled to whole numbers,
+// with the variables that the search of part 2 has to try values for
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let mut text = String::new();
+    for (i, (buttons, joltage)) in input.lines().map(parse_buttons_and_joltage).enumerate() {
+        let (system, _) = joltage_system(&buttons, &joltage);
+        text.push_str(&format!(
+            "Machine {}: pivot variables {:?}, free variables {:?}{}\n",
+            i + 1,
+            system.pivot_variables(),
+            system.free_variables(),
+            if system.is_consistent() {
+                ""
+            } else {
+                ", no solution"
+            }
+        ));
+        for row in system.rref() {
+            let scale = row.iter().fold(1, |lcm, x| {
+                lcm / gcd(lcm, x.denominator()) * x.denominator()
+            });
+            let values: Vec<String> = row
+                .iter()
+                .map(|x| (x.numerator() * (scale / x.denominator())).to_string())
+                .collect();
+            let (constant, coefficients) = values.split_last().unwrap();
+            text.push_str(&format!("  {} | {}\n", coefficients.join(" "), constant));
+        }
+    }
+    visualizer.text("systems", &text);
+}
+
 // A* worked fine for example. Not good enough for actual input
 #[allow(dead_code)]
 fn fewest_button_presses_for_joltage_a_star(buttons: Buttons, joltage: Joltage) -> usize {
@@ -194,37 +283,35 @@ fn fewest_button_presses_for_joltage_a_star(buttons: Buttons, joltage: Joltage)
 fn fewest_button_presses_for_joltage_bifurcation(
     buttons: &Buttons,
     joltage: Joltage,
-    cache: &mut HashMap<Joltage, usize>,
-    button_combinations_cache: &mut HashMap<u16, Vec<Vec<usize>>>,
+    memo: &mut Memo<Joltage, usize>,
+    button_combinations_memo: &mut Memo<BitSet, Vec<Vec<usize>>>,
 ) -> usize {
     if joltage.iter().all(|c| *c == 0) {
         return 0;
     }
-    if let Some(result) = cache.get(&joltage) {
-        return *result;
-    }
 
-    // Convert odd joltage counts to target lights to use as input for part 1 style solver
-    let lights: Lights = joltage.iter().map(|counter| counter % 2 == 1).collect();
-
-    let mut min = usize::MAX;
-    for combination in all_combinations_for_lights(&buttons, lights, button_combinations_cache) {
-        let presses = combination.len() as usize;
-        if let Some(next_joltages) = next_joltages_bifurcation(buttons, &joltage, combination) {
-            let recursion = fewest_button_presses_for_joltage_bifurcation(
-                buttons,
-                next_joltages,
-                cache,
-                button_combinations_cache,
-            );
-            if recursion.overflowing_mul(2).1 {
-                continue;
+    memo.get_or_compute(joltage.clone(), |memo| {
+        // Convert odd joltage counts to target lights to use as input for part 1 style solver
+        let lights: Lights = joltage.iter().map(|counter| counter % 2 == 1).collect();
+
+        let mut min = usize::MAX;
+        for combination in all_combinations_for_lights(&buttons, lights, button_combinations_memo) {
+            let presses = combination.len() as usize;
+            if let Some(next_joltages) = next_joltages_bifurcation(buttons, &joltage, combination) {
+                let recursion = fewest_button_presses_for_joltage_bifurcation(
+                    buttons,
+                    next_joltages,
+                    memo,
+                    button_combinations_memo,
+                );
+                if recursion.overflowing_mul(2).1 {
+                    continue;
+                }
+                min = min.min(2 * recursion + presses);
             }
-            min = min.min(2 * recursion + presses);
         }
-    }
-    cache.insert(joltage, min);
-    min
+        min
+    })
 }
 
 fn next_joltages_bifurcation(
@@ -251,37 +338,32 @@ fn next_joltages_bifurcation(
 fn all_combinations_for_lights(
     buttons: &Buttons,
     lights: Lights,
-    button_combinations_cache: &mut HashMap<u16, Vec<Vec<usize>>>,
+    button_combinations_memo: &mut Memo<BitSet, Vec<Vec<usize>>>,
 ) -> Vec<Vec<usize>> {
-    // Convert list of booleans into one number for easy XOR
-    let target_state: u16 = lights
-        .iter()
-        .rev()
-        .fold(0, |acc, b| if *b { (acc << 1) + 1 } else { acc << 1 });
-    if let Some(res) = button_combinations_cache.get(&target_state) {
-        return res.clone();
-    }
+    // Convert list of booleans into a bitset for easy XOR
+    let target_state = BitSet::from_bools(&lights);
+    button_combinations_memo.get_or_compute(target_state.clone(), |_| {
+        combinations_for_state(buttons, &target_state)
+    })
+}
 
+fn combinations_for_state(buttons: &Buttons, target_state: &BitSet) -> Vec<Vec<usize>> {
     // Will check each possible combination, starting with each button by itself
     let mut candidates: VecDeque<Vec<usize>> = (0..buttons.len()).map(|i| vec![i]).collect();
     let mut combinations: Vec<Vec<usize>> = Vec::new();
 
-    if target_state == 0 {
+    if target_state.is_empty() {
         // not pressing any button is also an option
         combinations.push(Vec::new());
     }
-    // Convert buttons from nested vecs of indices to vec of u16 for XORing with
-    let buttons: Vec<u16> = buttons
-        .iter()
-        .map(|button| button.iter().fold(0, |acc, i| acc + (1 << i)))
-        .collect();
+    let buttons = buttons_to_bit_sets(buttons, target_state.width());
 
     while let Some(combination) = candidates.pop_front() {
-        let mut state: u16 = 0;
+        let mut state = BitSet::new(target_state.width());
         for button_index in &combination {
-            state ^= buttons[*button_index];
+            state ^= &buttons[*button_index];
         }
-        if state == target_state {
+        if state == *target_state {
             combinations.push(combination.clone());
         }
         // Form next combinations by adding buttons after the last one used in the current combination
@@ -290,13 +372,14 @@ fn all_combinations_for_lights(
             candidates.push_back(combination.iter().copied().chain([j].into_iter()).collect());
         }
     }
-    button_combinations_cache.insert(target_state, combinations.clone());
     combinations
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::util::differential::{assert_agree, shrink_number, shrink_vec};
     use crate::util::util::load_input;
+    use rand::Rng;
 
     use super::*;
 
@@ -319,8 +402,83 @@ mod tests {
         assert_eq!(part_2(EXAMPLE_INPUT_1), 33); // 10 + 12 + 11
     }
 
+    #[test]
+    fn test_part_2_linear_system_example() {
+        let presses: Vec<usize> = EXAMPLE_INPUT_1
+            .lines()
+            .map(parse_buttons_and_joltage)
+            .map(|(buttons, joltage)| {
+                let (system, upper_bounds) = joltage_system(&buttons, &joltage);
+                fewest_button_presses_for_joltage_linear_system(&system, &upper_bounds)
+            })
+            .collect();
+        assert_eq!(presses, vec![10, 12, 11]);
+    }
+
     #[test]
     fn test_part_2() {
         assert_eq!(part_2(&load_input("inputs/2025/day_10")), 18981);
     }
+
+    // Machine as a list of buttons with how many times each is pressed, so there always is a solution
+    fn machine(presses: &[(Vec<u8>, usize)]) -> (Buttons, Joltage) {
+        let nr_of_counters = presses
+            .iter()
+            .flat_map(|(button, _)| button.iter().map(|&i| i as usize + 1))
+            .max()
+            .unwrap_or(0);
+        let mut joltage = vec![0; nr_of_counters];
+        for (button, n) in presses {
+            button
+                .iter()
+                .for_each(|&i| joltage[i as usize] += *n as u16);
+        }
+        (presses.iter().map(|(b, _)| b.clone()).collect(), joltage)
+    }
+
+    #[test]
+    fn test_part_2_differential() {
+        assert_agree(
+            100,
+            |rng| {
+                let nr_of_counters = rng.gen_range(1..5);
+                (0..rng.gen_range(1..5))
+                    .map(|_| {
+                        let mut button: Vec<u8> =
+                            (0..nr_of_counters).filter(|_| rng.gen_bool(0.5)).collect();
+                        if button.is_empty() {
+                            button.push(rng.gen_range(0..nr_of_counters));
+                        }
+                        (button, rng.gen_range(0..6))
+                    })
+                    .collect::<Vec<(Vec<u8>, usize)>>()
+            },
+            |presses| {
+                shrink_vec(presses, |(button, n)| {
+                    let fewer_presses = shrink_number(*n).into_iter().map(|n| (button.clone(), n));
+                    let smaller_buttons = shrink_vec(button, |_| vec![])
+                        .into_iter()
+                        .filter(|b| !b.is_empty())
+                        .map(|b| (b, *n));
+                    fewer_presses.chain(smaller_buttons).collect()
+                })
+                .into_iter()
+                .filter(|p| !p.is_empty())
+                .collect()
+            },
+            |presses| {
+                let (buttons, joltage) = machine(presses);
+                fewest_button_presses_for_joltage_a_star(buttons, joltage)
+            },
+            |presses| {
+                let (buttons, joltage) = machine(presses);
+                fewest_button_presses_for_joltage_bifurcation(
+                    &buttons,
+                    joltage,
+                    &mut Memo::new(),
+                    &mut Memo::new(),
+                )
+            },
+        );
+    }
 }
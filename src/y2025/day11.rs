@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use crate::util::graph::Graph;
+use crate::util::visualize::Visualizer;
 
 // How many different paths lead from `you` to `out`?
 pub fn part_1(input: &str) -> usize {
@@ -12,7 +13,7 @@ pub fn part_1(input: &str) -> usize {
     Path length from `you` to `out` seems to be 7. Simple DFS should be fine for part 1.
     */
     let graph = parse_graph(input);
-    nr_of_paths(&graph, &mut HashMap::new(), "you", "out")
+    nr_of_paths(&graph, "you", "out")
 }
 
 // First bruteforce solution for part 1 was just was fast as the final solution with memoization (0.2ms)
@@ -32,47 +33,24 @@ pub fn part_1(input: &str) -> usize {
 // }
 // result
 
-fn parse_graph(input: &str) -> HashMap<&str, Vec<&str>> {
-    input
-        .lines()
-        .map(|line| {
-            let (source, targets) = line.split_once(": ").unwrap();
-            (
-                source,
-                targets
-                    .split_whitespace()
-                    .map(|target| target)
-                    .collect::<Vec<&str>>(),
-            )
-        })
-        .collect()
+fn parse_graph(input: &str) -> Graph {
+    let mut graph = Graph::directed();
+    for line in input.lines() {
+        let (source, targets) = line.split_once(": ").unwrap();
+        for target in targets.split_whitespace() {
+            graph.add_edge(source, target);
+        }
+    }
+    graph
 }
 
-fn nr_of_paths<'a>(
-    graph: &'a HashMap<&str, Vec<&str>>,
-    cache: &mut HashMap<&'a str, usize>,
-    source: &'a str,
-    target: &str,
-) -> usize {
-    if let Some(count) = cache.get(source) {
-        return *count;
-    } else {
-        let count = if let Some(next_nodes) = graph.get(source) {
-            next_nodes
-                .iter()
-                .map(|next| {
-                    if *next == target {
-                        1
-                    } else {
-                        nr_of_paths(graph, cache, *next, target)
-                    }
-                })
-                .sum()
-        } else {
-            0
-        };
-        cache.insert(source, count);
-        return count;
+// Devices that are not in the input have no paths between them
+fn nr_of_paths(graph: &Graph, source: &str, target: &str) -> usize {
+    match (graph.id(source), graph.id(target)) {
+        (Some(source), Some(target)) => graph
+            .count_paths(source, target)
+            .expect("Devices are connected in a cycle"),
+        _ => 0,
     }
 }
 
@@ -83,9 +61,23 @@ pub fn part_2(input: &str) -> usize {
     // dac is between layers 5 and 6
     let graph = parse_graph(input);
 
-    nr_of_paths(&graph, &mut HashMap::new(), "svr", "fft")
-        * nr_of_paths(&graph, &mut HashMap::new(), "fft", "dac")
-        * nr_of_paths(&graph, &mut HashMap::new(), "dac", "out")
+    nr_of_paths(&graph, "svr", "fft")
+        * nr_of_paths(&graph, "fft", "dac")
+        * nr_of_paths(&graph, "dac", "out")
+}
+
+// The devices as dot file, and the nr of paths of each leg of the path of part 2
+pub fn visualize(input: &str, visualizer: &mut Visualizer) {
+    let graph = parse_graph(input);
+    let lines: String = [("svr", "fft"), ("fft", "dac"), ("dac", "out")]
+        .into_iter()
+        .map(|(source, target)| {
+            let paths = nr_of_paths(&graph, source, target);
+            format!("{source} -> {target}: {paths} paths\n")
+        })
+        .collect();
+    visualizer.text("paths", &lines);
+    visualizer.graph("devices", &graph);
 }
 
 #[cfg(test)]